@@ -0,0 +1,187 @@
+use lambdaworks_crypto::merkle_tree::traits::IsMerkleTreeBackend;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        errors::FieldError,
+        traits::{IsField, IsSubFieldOf},
+    },
+    traits::AsBytes,
+};
+use sha3::{Digest, Keccak256};
+
+use crate::{F, FE};
+
+// X^4 - 11 is irreducible over BabyBear
+const W: u64 = 11;
+
+// degree 4 extension F[X] / (X^4 - 11), [a0, a1, a2, a3] is a0 + a1 X + a2 X^2 + a3 X^3
+#[derive(Clone, Debug)]
+pub struct Degree4BabybearExtensionField;
+
+pub type E = Degree4BabybearExtensionField;
+pub type EFE = FieldElement<E>;
+
+fn mul_by_w(a: &FE) -> FE {
+    a * FE::from(W)
+}
+
+impl IsField for Degree4BabybearExtensionField {
+    type BaseType = [FE; 4];
+
+    fn add(a: &[FE; 4], b: &[FE; 4]) -> [FE; 4] {
+        [&a[0] + &b[0], &a[1] + &b[1], &a[2] + &b[2], &a[3] + &b[3]]
+    }
+
+    fn mul(a: &[FE; 4], b: &[FE; 4]) -> [FE; 4] {
+        [
+            &a[0] * &b[0] + mul_by_w(&(&a[1] * &b[3] + &a[2] * &b[2] + &a[3] * &b[1])),
+            &a[0] * &b[1] + &a[1] * &b[0] + mul_by_w(&(&a[2] * &b[3] + &a[3] * &b[2])),
+            &a[0] * &b[2] + &a[1] * &b[1] + &a[2] * &b[0] + mul_by_w(&(&a[3] * &b[3])),
+            &a[0] * &b[3] + &a[1] * &b[2] + &a[2] * &b[1] + &a[3] * &b[0],
+        ]
+    }
+
+    fn sub(a: &[FE; 4], b: &[FE; 4]) -> [FE; 4] {
+        [&a[0] - &b[0], &a[1] - &b[1], &a[2] - &b[2], &a[3] - &b[3]]
+    }
+
+    fn neg(a: &[FE; 4]) -> [FE; 4] {
+        [-&a[0], -&a[1], -&a[2], -&a[3]]
+    }
+
+    // with Y = X^2, a = e(Y) + X o(Y) and a * (e - X o) = e^2 - Y o^2 lies in F[Y] / (Y^2 - 11),
+    // where c + dY is inverted through its own conjugate c - dY
+    fn inv(a: &[FE; 4]) -> Result<[FE; 4], FieldError> {
+        let c = a[0].square() + mul_by_w(&a[2].square()) - mul_by_w(&(FE::from(2) * &a[1] * &a[3]));
+        let d = FE::from(2) * &a[0] * &a[2] - a[1].square() - mul_by_w(&a[3].square());
+        let norm_inv = (c.square() - mul_by_w(&d.square())).inv()?;
+
+        let conjugate = [a[0].clone(), -&a[1], a[2].clone(), -&a[3]];
+        let quadratic_inv = [&c * &norm_inv, FE::zero(), -&d * &norm_inv, FE::zero()];
+        Ok(<Self as IsField>::mul(&conjugate, &quadratic_inv))
+    }
+
+    fn div(a: &[FE; 4], b: &[FE; 4]) -> [FE; 4] {
+        <Self as IsField>::mul(a, &Self::inv(b).unwrap())
+    }
+
+    fn eq(a: &[FE; 4], b: &[FE; 4]) -> bool {
+        a == b
+    }
+
+    fn zero() -> [FE; 4] {
+        [FE::zero(), FE::zero(), FE::zero(), FE::zero()]
+    }
+
+    fn one() -> [FE; 4] {
+        [FE::one(), FE::zero(), FE::zero(), FE::zero()]
+    }
+
+    fn from_u64(x: u64) -> [FE; 4] {
+        [FE::from(x), FE::zero(), FE::zero(), FE::zero()]
+    }
+
+    fn from_base_type(x: [FE; 4]) -> [FE; 4] {
+        x
+    }
+}
+
+impl IsSubFieldOf<Degree4BabybearExtensionField> for F {
+    fn mul(a: &Self::BaseType, b: &[FE; 4]) -> [FE; 4] {
+        let a = FE::from_raw(*a);
+        [&a * &b[0], &a * &b[1], &a * &b[2], &a * &b[3]]
+    }
+
+    fn add(a: &Self::BaseType, b: &[FE; 4]) -> [FE; 4] {
+        [
+            FE::from_raw(*a) + &b[0],
+            b[1].clone(),
+            b[2].clone(),
+            b[3].clone(),
+        ]
+    }
+
+    fn div(a: &Self::BaseType, b: &[FE; 4]) -> [FE; 4] {
+        let b_inv = E::inv(b).unwrap();
+        <F as IsSubFieldOf<E>>::mul(a, &b_inv)
+    }
+
+    fn sub(a: &Self::BaseType, b: &[FE; 4]) -> [FE; 4] {
+        [FE::from_raw(*a) - &b[0], -&b[1], -&b[2], -&b[3]]
+    }
+
+    fn embed(a: Self::BaseType) -> [FE; 4] {
+        [FE::from_raw(a), FE::zero(), FE::zero(), FE::zero()]
+    }
+
+    fn to_subfield_vec(b: [FE; 4]) -> Vec<Self::BaseType> {
+        b.into_iter()
+            .map(|coefficient| coefficient.to_raw())
+            .collect()
+    }
+}
+
+// the batch keccak backend of lambdaworks only hashes field elements that are AsBytes,
+// extension leaves are hashed coefficient by coefficient
+#[derive(Clone, Default)]
+pub struct BatchedExtensionMerkleTreeBackend;
+
+impl IsMerkleTreeBackend for BatchedExtensionMerkleTreeBackend {
+    type Node = [u8; 32];
+    type Data = Vec<EFE>;
+
+    fn hash_data(leaf: &Vec<EFE>) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for element in leaf {
+            for coefficient in element.value() {
+                hasher.update(coefficient.as_bytes());
+            }
+        }
+        hasher.finalize().into()
+    }
+
+    fn hash_new_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        Keccak256::new()
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(seed: u64) -> EFE {
+        EFE::new([
+            FE::from(seed),
+            FE::from(seed * 7 + 1),
+            FE::from(seed * seed + 5),
+            FE::from(3 * seed + 2),
+        ])
+    }
+
+    #[test]
+    fn x_to_the_fourth_is_w() {
+        let x = EFE::new([FE::zero(), FE::one(), FE::zero(), FE::zero()]);
+        assert_eq!(x.pow(4u64), EFE::from(W));
+    }
+
+    #[test]
+    fn inverse_of_nonzero_elements() {
+        for seed in [1, 2, 12345, 1 << 30] {
+            let a = element(seed);
+            assert_eq!(&a * a.inv().unwrap(), EFE::one());
+        }
+        assert!(EFE::zero().inv().is_err());
+    }
+
+    #[test]
+    fn base_field_embeds_into_the_extension() {
+        let a = FE::from(99);
+        let b = element(4);
+        assert_eq!(&a * &b, a.clone().to_extension::<E>() * &b);
+        assert_eq!(&a + &b - &b, a.to_extension::<E>());
+    }
+}
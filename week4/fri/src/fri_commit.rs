@@ -1,12 +1,101 @@
-use crate::BatchedMerkleTree;
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
 use lambdaworks_math::{
-    field::{element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField},
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsSubFieldOf},
+    },
     polynomial::Polynomial,
 };
 
-pub fn _commit(
-    _betas: Vec<FieldElement<Babybear31PrimeField>>,
-    _polynomial: Polynomial<FieldElement<Babybear31PrimeField>>,
-) -> Vec<BatchedMerkleTree<Babybear31PrimeField>> {
-    todo!()
+use crate::{
+    extension::{BatchedExtensionMerkleTreeBackend, E, EFE},
+    fold_polynomial::fold_polynomial,
+    transcript::Transcript,
+    BatchedMerkleTreeBackend, FriConfig, F, FE,
+};
+
+// evaluations of one folded polynomial, leaf j of the tree holds the pair (f(x_j), f(-x_j))
+pub struct FriLayer<L: IsField, B: IsMerkleTreeBackend> {
+    pub evaluations: Vec<FieldElement<L>>,
+    pub merkle_tree: MerkleTree<B>,
+}
+
+// the first layer holds the base field evaluations of the input polynomial, every fold with
+// an extension beta moves the following layers to the extension
+pub struct FriCommitment {
+    pub first_layer: FriLayer<F, BatchedMerkleTreeBackend<F>>,
+    pub layers: Vec<FriLayer<E, BatchedExtensionMerkleTreeBackend>>,
+    pub last_value: EFE,
+}
+
+// the second half of a domain offset * <w> is the negation of the first one
+pub fn paired_leaves<L: IsField>(evaluations: &[FieldElement<L>]) -> Vec<Vec<FieldElement<L>>> {
+    let (low, high) = evaluations.split_at(evaluations.len() / 2);
+    low.iter()
+        .zip(high)
+        .map(|(x, x_neg)| vec![x.clone(), x_neg.clone()])
+        .collect()
+}
+
+fn commit_layer<L, B>(
+    poly: &Polynomial<FieldElement<L>>,
+    offset: &FE,
+    domain_size: usize,
+    transcript: &mut Transcript,
+) -> FriLayer<L, B>
+where
+    L: IsField,
+    F: IsSubFieldOf<L>,
+    B: IsMerkleTreeBackend<Node = [u8; 32], Data = Vec<FieldElement<L>>>,
+{
+    let evaluations =
+        Polynomial::evaluate_offset_fft::<F>(poly, 1, Some(domain_size), offset).unwrap();
+    let merkle_tree = MerkleTree::<B>::build(&paired_leaves(&evaluations));
+    transcript.append_bytes(&merkle_tree.root);
+    FriLayer {
+        evaluations,
+        merkle_tree,
+    }
+}
+
+// absorbs the config, then commits to log_degree_bound layers, folding with a beta drawn after each layer root,
+// until a polynomial of degree below the bound collapses to a constant
+pub fn commit(
+    polynomial: &Polynomial<FE>,
+    config: &FriConfig,
+    transcript: &mut Transcript,
+) -> FriCommitment {
+    assert!(config.log_degree_bound > 0);
+    config.append_to_transcript(transcript);
+    let mut offset = config.coset_offset.clone();
+    let mut domain_size = config.domain_size();
+
+    let first_layer = commit_layer(polynomial, &offset, domain_size, transcript);
+    let lifted: Vec<EFE> = polynomial
+        .coefficients()
+        .iter()
+        .map(|coefficient| coefficient.clone().to_extension())
+        .collect();
+    let mut poly = fold_polynomial(
+        &Polynomial::new(&lifted),
+        &transcript.sample_extension_element(),
+    );
+
+    let mut layers = vec![];
+    for _ in 1..config.log_degree_bound {
+        offset = offset.square();
+        domain_size /= 2;
+        layers.push(commit_layer(&poly, &offset, domain_size, transcript));
+        poly = fold_polynomial(&poly, &transcript.sample_extension_element());
+    }
+
+    // an honest prover is left with a constant, anything else fails the query checks
+    let last_value = poly.coefficients().first().cloned().unwrap_or(EFE::zero());
+    transcript.append_extension_element(&last_value);
+
+    FriCommitment {
+        first_layer,
+        layers,
+        last_value,
+    }
 }
@@ -1 +1,84 @@
+use lambdaworks_crypto::merkle_tree::{proof::Proof, traits::IsMerkleTreeBackend};
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
+use crate::{
+    extension::{E, EFE},
+    fri_commit::{FriCommitment, FriLayer},
+    transcript::Transcript,
+    F,
+};
+
+pub struct LayerOpening<L: IsField> {
+    pub evaluation: FieldElement<L>,
+    pub sibling: FieldElement<L>,
+    pub auth_path: Proof<[u8; 32]>,
+}
+
+// openings of f(x) and f(-x) in every layer for one query
+pub struct QueryDecommitment {
+    pub first_layer: LayerOpening<F>,
+    pub layers: Vec<LayerOpening<E>>,
+}
+
+pub struct FriProof {
+    pub layer_roots: Vec<[u8; 32]>,
+    pub last_value: EFE,
+    pub decommitments: Vec<QueryDecommitment>,
+}
+
+// samples the query positions in the first layer once the whole commitment is absorbed
+pub fn sample_queries(
+    transcript: &mut Transcript,
+    number_of_queries: usize,
+    domain_size: usize,
+) -> Vec<usize> {
+    (0..number_of_queries)
+        .map(|_| transcript.sample_index(domain_size))
+        .collect()
+}
+
+fn open<L, B>(layer: &FriLayer<L, B>, query: usize) -> LayerOpening<L>
+where
+    L: IsField,
+    B: IsMerkleTreeBackend<Node = [u8; 32]>,
+{
+    let size = layer.evaluations.len();
+    let index = query % size;
+    LayerOpening {
+        evaluation: layer.evaluations[index].clone(),
+        sibling: layer.evaluations[(index + size / 2) % size].clone(),
+        auth_path: layer
+            .merkle_tree
+            .get_proof_by_pos(index % (size / 2))
+            .unwrap(),
+    }
+}
+
+pub fn query(
+    commitment: &FriCommitment,
+    number_of_queries: usize,
+    transcript: &mut Transcript,
+) -> FriProof {
+    let domain_size = commitment.first_layer.evaluations.len();
+    let queries = sample_queries(transcript, number_of_queries, domain_size);
+
+    let decommitments = queries
+        .iter()
+        .map(|query| QueryDecommitment {
+            first_layer: open(&commitment.first_layer, *query),
+            layers: commitment
+                .layers
+                .iter()
+                .map(|layer| open(layer, *query))
+                .collect(),
+        })
+        .collect();
+
+    FriProof {
+        layer_roots: std::iter::once(commitment.first_layer.merkle_tree.root)
+            .chain(commitment.layers.iter().map(|layer| layer.merkle_tree.root))
+            .collect(),
+        last_value: commitment.last_value.clone(),
+        decommitments,
+    }
+}
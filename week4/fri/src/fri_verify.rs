@@ -0,0 +1,281 @@
+use std::fmt;
+
+use lambdaworks_crypto::merkle_tree::traits::IsMerkleTreeBackend;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+use crate::{
+    extension::{BatchedExtensionMerkleTreeBackend, E, EFE},
+    fri_decommit::{sample_queries, FriProof, LayerOpening},
+    transcript::Transcript,
+    BatchedMerkleTreeBackend, FriConfig, F, FE,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FriError {
+    LayerCountMismatch { expected: usize, found: usize },
+    QueryCountMismatch { expected: usize, found: usize },
+    InvalidMerklePath { layer: usize, query: usize },
+    FoldingMismatch { layer: usize, query: usize },
+}
+
+impl fmt::Display for FriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FriError::LayerCountMismatch { expected, found } => {
+                write!(f, "expected {expected} fri layers, found {found}")
+            }
+            FriError::QueryCountMismatch { expected, found } => {
+                write!(f, "expected openings for {expected} queries, found {found}")
+            }
+            FriError::InvalidMerklePath { layer, query } => {
+                write!(f, "invalid merkle path in layer {layer} for query {query}")
+            }
+            FriError::FoldingMismatch { layer, query } => {
+                write!(f, "folding mismatch in layer {layer} for query {query}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FriError {}
+
+// g(x^2) from f(x) and f(-x): even part plus beta times odd part
+fn fold_pair<L>(evaluation: &FieldElement<L>, sibling: &FieldElement<L>, x: &FE, beta: &EFE) -> EFE
+where
+    L: IsField + IsSubFieldOf<E>,
+    F: IsSubFieldOf<L>,
+{
+    let two_inv = FE::from(2).inv().unwrap();
+    let even = &two_inv * (evaluation + sibling);
+    let odd = two_inv * x.inv().unwrap() * (evaluation - sibling);
+    even.to_extension::<E>() + odd * beta
+}
+
+// checks the opening of x = offset * w^index in a layer of the given size and folds it,
+// None when the authentication path does not match the root
+fn check_and_fold<L, B>(
+    opening: &LayerOpening<L>,
+    root: &[u8; 32],
+    index: usize,
+    x: &FE,
+    size: usize,
+    beta: &EFE,
+) -> Option<EFE>
+where
+    L: IsField + IsSubFieldOf<E>,
+    F: IsSubFieldOf<L>,
+    B: IsMerkleTreeBackend<Node = [u8; 32], Data = Vec<FieldElement<L>>>,
+{
+    let leaf = if index < size / 2 {
+        vec![opening.evaluation.clone(), opening.sibling.clone()]
+    } else {
+        vec![opening.sibling.clone(), opening.evaluation.clone()]
+    };
+    opening
+        .auth_path
+        .verify::<B>(root, index % (size / 2), &leaf)
+        .then(|| fold_pair(&opening.evaluation, &opening.sibling, x, beta))
+}
+
+// `transcript` must be in the state the prover's was when it called `commit`
+pub fn verify(
+    proof: &FriProof,
+    config: &FriConfig,
+    transcript: &mut Transcript,
+) -> Result<(), FriError> {
+    if proof.layer_roots.len() != config.log_degree_bound {
+        return Err(FriError::LayerCountMismatch {
+            expected: config.log_degree_bound,
+            found: proof.layer_roots.len(),
+        });
+    }
+
+    config.append_to_transcript(transcript);
+    let betas: Vec<EFE> = proof
+        .layer_roots
+        .iter()
+        .map(|root| {
+            transcript.append_bytes(root);
+            transcript.sample_extension_element()
+        })
+        .collect();
+    transcript.append_extension_element(&proof.last_value);
+    let queries = sample_queries(transcript, config.number_of_queries, config.domain_size());
+
+    if proof.decommitments.len() != queries.len() {
+        return Err(FriError::QueryCountMismatch {
+            expected: queries.len(),
+            found: proof.decommitments.len(),
+        });
+    }
+
+    for (n, (query, decommitment)) in queries.iter().zip(&proof.decommitments).enumerate() {
+        if decommitment.layers.len() + 1 != betas.len() {
+            return Err(FriError::LayerCountMismatch {
+                expected: betas.len(),
+                found: decommitment.layers.len() + 1,
+            });
+        }
+
+        let mut offset = config.coset_offset.clone();
+        let mut size = config.domain_size();
+        let index = query % size;
+        let x = &offset * config.layer_generator(0).pow(index);
+        let mut folded = check_and_fold::<F, BatchedMerkleTreeBackend<F>>(
+            &decommitment.first_layer,
+            &proof.layer_roots[0],
+            index,
+            &x,
+            size,
+            &betas[0],
+        )
+        .ok_or(FriError::InvalidMerklePath { layer: 0, query: n })?;
+
+        for (i, opening) in decommitment.layers.iter().enumerate() {
+            let layer = i + 1;
+            offset = offset.square();
+            size /= 2;
+            if folded != opening.evaluation {
+                return Err(FriError::FoldingMismatch { layer, query: n });
+            }
+
+            let index = query % size;
+            let x = &offset * config.layer_generator(layer).pow(index);
+            folded = check_and_fold::<E, BatchedExtensionMerkleTreeBackend>(
+                opening,
+                &proof.layer_roots[layer],
+                index,
+                &x,
+                size,
+                &betas[layer],
+            )
+            .ok_or(FriError::InvalidMerklePath { layer, query: n })?;
+        }
+
+        if folded != proof.last_value {
+            return Err(FriError::FoldingMismatch {
+                layer: betas.len(),
+                query: n,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::polynomial::Polynomial;
+
+    use super::*;
+    use crate::{fri_commit::commit, fri_decommit::query};
+
+    fn config() -> FriConfig {
+        FriConfig {
+            log_degree_bound: 6,
+            log_blowup_factor: 2,
+            number_of_queries: 8,
+            coset_offset: FE::from(7),
+        }
+    }
+
+    fn polynomial(degree: usize) -> Polynomial<FE> {
+        let coefficients: Vec<FE> = (0..=degree as u64)
+            .map(|i| FE::from(i * i + 3 * i + 1))
+            .collect();
+        Polynomial::new(&coefficients)
+    }
+
+    fn prove(polynomial: &Polynomial<FE>, config: &FriConfig) -> FriProof {
+        let mut transcript = Transcript::new();
+        let commitment = commit(polynomial, config, &mut transcript);
+        query(&commitment, config.number_of_queries, &mut transcript)
+    }
+
+    #[test]
+    fn accepts_polynomials_below_the_degree_bound() {
+        let config = config();
+        for degree in [0, 1, 17, 63] {
+            assert_eq!(
+                verify(
+                    &prove(&polynomial(degree), &config),
+                    &config,
+                    &mut Transcript::new()
+                ),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_polynomials_exceeding_the_degree_bound() {
+        let config = config();
+        for degree in [64, 65, 100] {
+            assert!(matches!(
+                verify(
+                    &prove(&polynomial(degree), &config),
+                    &config,
+                    &mut Transcript::new()
+                ),
+                Err(FriError::FoldingMismatch { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_a_tampered_opening() {
+        let config = config();
+        let mut proof = prove(&polynomial(40), &config);
+        proof.decommitments[3].layers[1].sibling += EFE::one();
+
+        assert_eq!(
+            verify(&proof, &config, &mut Transcript::new()),
+            Err(FriError::InvalidMerklePath { layer: 2, query: 3 })
+        );
+    }
+
+    #[test]
+    fn rejects_a_proof_for_a_different_degree_bound() {
+        let proof = prove(&polynomial(40), &config());
+        let config = FriConfig {
+            log_degree_bound: 5,
+            ..config()
+        };
+
+        assert_eq!(
+            verify(&proof, &config, &mut Transcript::new()),
+            Err(FriError::LayerCountMismatch {
+                expected: 5,
+                found: 6
+            })
+        );
+    }
+
+    #[test]
+    fn verifier_replays_the_prover_context() {
+        let config = config();
+        let mut transcript = Transcript::new();
+        transcript.append_bytes(b"statement");
+        let commitment = commit(&polynomial(40), &config, &mut transcript);
+        let proof = query(&commitment, config.number_of_queries, &mut transcript);
+
+        let mut transcript = Transcript::new();
+        transcript.append_bytes(b"statement");
+        assert_eq!(verify(&proof, &config, &mut transcript), Ok(()));
+        assert!(verify(&proof, &config, &mut Transcript::new()).is_err());
+    }
+
+    #[test]
+    fn rejects_a_proof_for_another_coset_offset() {
+        let proof = prove(&polynomial(40), &config());
+        let config = FriConfig {
+            coset_offset: FE::from(11),
+            ..config()
+        };
+
+        assert!(verify(&proof, &config, &mut Transcript::new()).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+pub mod extension;
+pub mod fold_polynomial;
+pub mod fri_commit;
+pub mod fri_decommit;
+pub mod fri_verify;
+pub mod transcript;
+
+use lambdaworks_crypto::merkle_tree::backends::types::BatchKeccak256Backend;
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField, traits::IsFFTField,
+};
+
+use crate::transcript::Transcript;
+
+pub type BatchedMerkleTreeBackend<F> = BatchKeccak256Backend<F>;
+
+pub type F = Babybear31PrimeField;
+pub type FE = FieldElement<F>;
+
+// low degree test parameters shared by prover and verifier: the polynomial must have degree
+// below 2^log_degree_bound and is evaluated on offset * <w> of size 2^(log_degree_bound + log_blowup)
+#[derive(Clone, Debug)]
+pub struct FriConfig {
+    pub log_degree_bound: usize,
+    pub log_blowup_factor: usize,
+    pub number_of_queries: usize,
+    pub coset_offset: FE,
+}
+
+impl FriConfig {
+    pub fn domain_size(&self) -> usize {
+        1 << (self.log_degree_bound + self.log_blowup_factor)
+    }
+
+    // binds the proof to every parameter, the coset offset included, before the first root
+    pub fn append_to_transcript(&self, transcript: &mut Transcript) {
+        for parameter in [
+            self.log_degree_bound,
+            self.log_blowup_factor,
+            self.number_of_queries,
+        ] {
+            transcript.append_bytes(&(parameter as u64).to_be_bytes());
+        }
+        transcript.append_field_element(&self.coset_offset);
+    }
+
+    // generator of the evaluation domain of the given layer, halving in size at every fold
+    pub fn layer_generator(&self, layer: usize) -> FE {
+        let log_size = self.log_degree_bound + self.log_blowup_factor - layer;
+        F::get_primitive_root_of_unity(log_size as u64).unwrap()
+    }
+}
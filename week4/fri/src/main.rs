@@ -1,17 +1,23 @@
-mod fold_polynomial;
-mod fri_commit;
-mod fri_decommit;
-
-use lambdaworks_crypto::merkle_tree::{backends::types::BatchKeccak256Backend, merkle::MerkleTree};
-use lambdaworks_math::field::{
-    element::FieldElement, fields::fft_friendly::babybear::Babybear31PrimeField,
-};
-
-pub type BatchedMerkleTreeBackend<F> = BatchKeccak256Backend<F>;
-pub type BatchedMerkleTree<F> = MerkleTree<BatchedMerkleTreeBackend<F>>;
+use fri::{fri_commit::commit, fri_decommit::query, fri_verify::verify, transcript::Transcript};
+use fri::{FriConfig, FE};
+use lambdaworks_math::polynomial::Polynomial;
 
 fn main() {
-    let a = FieldElement::<Babybear31PrimeField>::from(10);
+    let config = FriConfig {
+        log_degree_bound: 10,
+        log_blowup_factor: 3,
+        number_of_queries: 32,
+        coset_offset: FE::from(31),
+    };
+    let coefficients: Vec<FE> = (0..1u64 << config.log_degree_bound).map(FE::from).collect();
+    let polynomial = Polynomial::new(&coefficients);
+
+    let mut transcript = Transcript::new();
+    let commitment = commit(&polynomial, &config, &mut transcript);
+    let proof = query(&commitment, config.number_of_queries, &mut transcript);
 
-    println!("{}", a.representative().to_hex())
+    match verify(&proof, &config, &mut Transcript::new()) {
+        Ok(()) => println!("fri proof accepted"),
+        Err(err) => println!("fri proof rejected: {err}"),
+    }
 }
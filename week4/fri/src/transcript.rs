@@ -0,0 +1,64 @@
+use lambdaworks_math::traits::AsBytes;
+use sha3::{Digest, Keccak256};
+
+use crate::{extension::EFE, FE};
+
+pub struct Transcript {
+    hasher: Keccak256,
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(b"fri");
+        Self { hasher }
+    }
+
+    pub fn append_bytes(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    pub fn append_field_element(&mut self, element: &FE) {
+        self.append_bytes(&element.as_bytes());
+    }
+
+    pub fn append_extension_element(&mut self, element: &EFE) {
+        for coefficient in element.value() {
+            self.append_field_element(coefficient);
+        }
+    }
+
+    fn challenge(&mut self) -> [u8; 32] {
+        let digest: [u8; 32] = self.hasher.finalize_reset().into();
+        self.hasher.update(digest);
+        digest
+    }
+
+    // 64 bits reduced modulo the 31 bit prime, the bias is negligible
+    pub fn sample_field_element(&mut self) -> FE {
+        FE::from(u64::from_be_bytes(
+            self.challenge()[..8].try_into().unwrap(),
+        ))
+    }
+
+    // challenges live in the degree 4 extension, a 31 bit field alone is too small for soundness
+    pub fn sample_extension_element(&mut self) -> EFE {
+        EFE::new([
+            self.sample_field_element(),
+            self.sample_field_element(),
+            self.sample_field_element(),
+            self.sample_field_element(),
+        ])
+    }
+
+    pub fn sample_index(&mut self, upper_bound: usize) -> usize {
+        (u64::from_be_bytes(self.challenge()[..8].try_into().unwrap()) % upper_bound as u64)
+            as usize
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+use crate::trace::TraceTable;
+
+use super::{Air, Frame};
+
+// first constraint found not to hold on the trace itself
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    Boundary { assertion: usize, row: usize },
+    Transition { constraint: usize, row: usize },
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstraintViolation::Boundary { assertion, row } => {
+                write!(
+                    f,
+                    "boundary assertion {assertion} does not hold at row {row}"
+                )
+            }
+            ConstraintViolation::Transition { constraint, row } => {
+                write!(
+                    f,
+                    "transition constraint {constraint} does not hold at row {row}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConstraintViolation {}
+
+// an air the prover and verifier cannot work with, whatever the trace
+#[derive(Debug, PartialEq, Eq)]
+pub enum AirError {
+    InvalidTransitionOffsets,
+    InvalidTransitionStep(usize),
+    TooManyTransitionExemptions(usize),
+    InvalidExemptedRows,
+}
+
+impl fmt::Display for AirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AirError::InvalidTransitionOffsets => {
+                write!(f, "transition offsets must start with 0")
+            }
+            AirError::InvalidTransitionStep(step) => write!(
+                f,
+                "transition step {step} is not a power of two dividing the trace length"
+            ),
+            AirError::TooManyTransitionExemptions(exemptions) => write!(
+                f,
+                "{exemptions} transition exemptions exceed the rows the constraints apply to"
+            ),
+            AirError::InvalidExemptedRows => write!(
+                f,
+                "exempted rows must be distinct rows the transition constraints apply to"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AirError {}
+
+// what the zerofier and the frames assume of the air: boundary constraints and the verifier
+// read the current row as the first one of the frame, and the exempted rows must be rows the
+// transition constraints apply to
+pub fn check_air<F, A>(air: &A) -> Result<(), AirError>
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    if air.transition_offsets().first() != Some(&0) {
+        return Err(AirError::InvalidTransitionOffsets);
+    }
+
+    let (trace_length, step) = (air.trace_length(), air.transition_step());
+    if !step.is_power_of_two() || !trace_length.is_multiple_of(step) {
+        return Err(AirError::InvalidTransitionStep(step));
+    }
+    // checked before the default exempted rows count back from the end of the trace
+    if air.transition_exemptions() > trace_length / step {
+        return Err(AirError::TooManyTransitionExemptions(
+            air.transition_exemptions(),
+        ));
+    }
+
+    let exempted_rows = air.transition_exempted_rows();
+    let mut rows = exempted_rows.clone();
+    rows.sort_unstable();
+    rows.dedup();
+    if rows.len() != exempted_rows.len()
+        || rows
+            .iter()
+            .any(|row| *row >= trace_length || !row.is_multiple_of(step))
+    {
+        return Err(AirError::InvalidExemptedRows);
+    }
+    Ok(())
+}
+
+// checks every constraint row by row, a composition polynomial built from a trace that fails
+// them is no polynomial at all and would only surface as a rejected proof
+pub fn check_trace<F, A>(air: &A, trace: &TraceTable<F>) -> Result<(), ConstraintViolation>
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    for (assertion, boundary) in air.boundary_assertions().iter().enumerate() {
+        if trace.row(boundary.row)[boundary.column] != boundary.value {
+            return Err(ConstraintViolation::Boundary {
+                assertion,
+                row: boundary.row,
+            });
+        }
+    }
+
+    let offsets = air.transition_offsets();
+    let periodic_columns = air.periodic_columns();
+    let exempted_rows = air.transition_exempted_rows();
+    for row in (0..air.trace_length())
+        .step_by(air.transition_step())
+        .filter(|row| !exempted_rows.contains(row))
+    {
+        let frame = Frame::read_from_trace(trace, row, &offsets);
+        let periodic_values: Vec<FieldElement<F>> = periodic_columns
+            .iter()
+            .map(|values| values[row % values.len()].clone())
+            .collect();
+        let constraints = air.evaluate_transition(&frame, &periodic_values);
+        if let Some(constraint) = constraints
+            .iter()
+            .position(|value| *value != FieldElement::zero())
+        {
+            return Err(ConstraintViolation::Transition { constraint, row });
+        }
+    }
+    Ok(())
+}
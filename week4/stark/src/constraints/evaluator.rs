@@ -0,0 +1,207 @@
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::fri::Coset;
+
+use super::{periodic_polys, Air, Frame};
+
+// Montgomery's trick: a single inversion and 3(n - 1) multiplications for n elements
+pub fn batch_inverse<F: IsField>(values: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    if values.is_empty() {
+        return vec![];
+    }
+
+    let mut prefix_products = Vec::with_capacity(values.len());
+    let mut acc = FieldElement::<F>::one();
+    for value in values {
+        prefix_products.push(acc.clone());
+        acc *= value;
+    }
+
+    let mut acc_inv = acc.inv().expect("Batch inversion of a zero element");
+    let mut inverses = vec![FieldElement::zero(); values.len()];
+    for i in (0..values.len()).rev() {
+        inverses[i] = &acc_inv * &prefix_products[i];
+        acc_inv *= &values[i];
+    }
+    inverses
+}
+
+// points evaluated per frame buffer, each chunk reuses one frame and one periodic row
+const CHUNK_SIZE: usize = 1024;
+
+// composition polynomial evaluations over the whole lde domain from the trace lde columns,
+// with the same alpha layout as `eval_composition_polynomial`
+pub fn evaluate_composition_over_lde<F, A>(
+    air: &A,
+    lde_columns: &[Vec<FieldElement<F>>],
+    lde_domain: &Coset<F>,
+    blowup_factor: usize,
+    alphas: &[FieldElement<F>],
+) -> Vec<FieldElement<F>>
+where
+    F: IsFFTField,
+    A: Air<F> + Sync,
+    FieldElement<F>: Send + Sync,
+{
+    let trace_length = air.trace_length();
+    let trace_generator = air.trace_generator();
+    let offsets = air.transition_offsets();
+    let assertions = air.boundary_assertions();
+    let (boundary_alphas, transition_alphas) = alphas.split_at(assertions.len());
+
+    let mut points = Vec::with_capacity(lde_domain.size);
+    let mut point = lde_domain.offset.clone();
+    for _ in 0..lde_domain.size {
+        points.push(point.clone());
+        point *= &lde_domain.generator;
+    }
+
+    let zerofier_inv = air
+        .transition_zerofier()
+        .inverse_evaluations_over_coset(lde_domain);
+
+    // over the lde coset x^(n/p) repeats every p * blowup_factor points, so a periodic column
+    // only needs that many evaluations of its polynomial
+    let periodic_evals: Vec<Vec<FieldElement<F>>> = air
+        .periodic_columns()
+        .iter()
+        .zip(periodic_polys(air))
+        .map(|(values, poly)| {
+            let offset = lde_domain.offset.pow(trace_length / values.len());
+            Polynomial::evaluate_offset_fft::<F>(&poly, blowup_factor, Some(values.len()), &offset)
+                .unwrap()
+        })
+        .collect();
+
+    // one batch of denominators x - g^row per boundary assertion, laid out assertion-major
+    let boundary_denominators: Vec<FieldElement<F>> = assertions
+        .iter()
+        .flat_map(|assertion| {
+            let row_point = trace_generator.pow(assertion.row);
+            points.iter().map(move |x| x - &row_point)
+        })
+        .collect();
+    let boundary_inv = batch_inverse(&boundary_denominators);
+
+    let chunks = points.len().div_ceil(CHUNK_SIZE);
+    into_iter!(0..chunks)
+        .map(|chunk| {
+            let mut frame = Frame::zeroed(offsets.len(), air.trace_width());
+            let mut periodic_values = vec![FieldElement::zero(); periodic_evals.len()];
+            let end = ((chunk + 1) * CHUNK_SIZE).min(points.len());
+
+            (chunk * CHUNK_SIZE..end)
+                .map(|i| {
+                    frame.fill_from_lde(lde_columns, i, &offsets, blowup_factor);
+                    for (value, evals) in periodic_values.iter_mut().zip(&periodic_evals) {
+                        *value = evals[i % evals.len()].clone();
+                    }
+
+                    let boundary = assertions.iter().zip(boundary_alphas).enumerate().fold(
+                        FieldElement::zero(),
+                        |acc, (j, (assertion, alpha))| {
+                            let value = &frame.get_row(0)[assertion.column];
+                            acc + alpha
+                                * (value - &assertion.value)
+                                * &boundary_inv[j * points.len() + i]
+                        },
+                    );
+
+                    let transition = air
+                        .evaluate_transition(&frame, &periodic_values)
+                        .iter()
+                        .zip(transition_alphas)
+                        .fold(FieldElement::zero(), |acc, (constraint, alpha)| {
+                            acc + alpha * constraint
+                        });
+
+                    boundary + transition * &zerofier_inv[i]
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use crate::{
+        constraints::{
+            eval_composition_polynomial,
+            fibonacci::{FibonacciAir, FibonacciPublicInputs},
+        },
+        trace::{compute_lde, fibonacci_trace},
+    };
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    #[test]
+    fn batch_inverse_of_nothing_is_empty() {
+        assert!(batch_inverse::<F>(&[]).is_empty());
+    }
+
+    #[test]
+    fn batch_inverse_matches_individual_inverses() {
+        let values: Vec<FE> = [1, 2, 3, 5, 1234567].map(FE::from).to_vec();
+        let inverses = batch_inverse(&values);
+        for (value, inverse) in values.iter().zip(&inverses) {
+            assert_eq!(*inverse, value.inv().unwrap());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch inversion of a zero element")]
+    fn batch_inverse_rejects_zero() {
+        batch_inverse(&[FE::from(3), FE::zero(), FE::from(7)]);
+    }
+
+    #[test]
+    fn lde_evaluation_matches_pointwise_composition() {
+        let trace_length = 16;
+        let blowup_factor = 4;
+        let trace = fibonacci_trace::<F>(trace_length);
+        let air = FibonacciAir {
+            trace_length,
+            public_inputs: FibonacciPublicInputs::from_trace(&trace),
+        };
+
+        let size = trace_length * blowup_factor;
+        let lde_domain = Coset {
+            generator: F::get_primitive_root_of_unity(size.trailing_zeros() as u64).unwrap(),
+            offset: FE::from(3),
+            size,
+        };
+        let trace_polys = trace.interpolate_columns();
+        let lde_columns = compute_lde(&trace_polys, &lde_domain);
+        let alphas: Vec<FE> = (1..=air.num_constraints() as u64).map(FE::from).collect();
+
+        let evaluations =
+            evaluate_composition_over_lde(&air, &lde_columns, &lde_domain, blowup_factor, &alphas);
+
+        let offsets = air.transition_offsets();
+        assert_eq!(evaluations.len(), size);
+        for (i, evaluation) in evaluations.iter().enumerate() {
+            let point = lde_domain.element(i);
+            let frame =
+                Frame::read_from_polys(&trace_polys, &point, &air.trace_generator(), &offsets);
+            assert_eq!(
+                *evaluation,
+                eval_composition_polynomial(&air, &frame, &point, &alphas)
+            );
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField},
+};
+
+use crate::trace::TraceTable;
+
+use super::{Air, BoundaryAssertion, Frame};
+
+// the two starting values and the claimed value of the last row
+#[derive(Clone, Debug)]
+pub struct FibonacciPublicInputs<F: IsField> {
+    pub first: FieldElement<F>,
+    pub second: FieldElement<F>,
+    pub result: FieldElement<F>,
+}
+
+impl<F: IsField> FibonacciPublicInputs<F> {
+    pub fn from_trace(trace: &TraceTable<F>) -> Self {
+        FibonacciPublicInputs {
+            first: trace.row(0)[0].clone(),
+            second: trace.row(1)[0].clone(),
+            result: trace.row(trace.n_rows() - 1)[0].clone(),
+        }
+    }
+}
+
+// f(g^2 x) = f(gx) + f(x) with f(1), f(g) and f(g^(n-1)) given by the public inputs
+pub struct FibonacciAir<F: IsField> {
+    pub trace_length: usize,
+    pub public_inputs: FibonacciPublicInputs<F>,
+}
+
+impl<F: IsFFTField> Air<F> for FibonacciAir<F> {
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn trace_width(&self) -> usize {
+        1
+    }
+
+    fn transition_offsets(&self) -> Vec<usize> {
+        vec![0, 1, 2]
+    }
+
+    fn transition_exemptions(&self) -> usize {
+        2
+    }
+
+    fn evaluate_transition(
+        &self,
+        frame: &Frame<F>,
+        _periodic_values: &[FieldElement<F>],
+    ) -> Vec<FieldElement<F>> {
+        vec![&frame.get_row(2)[0] - &frame.get_row(1)[0] - &frame.get_row(0)[0]]
+    }
+
+    fn transition_degrees(&self) -> Vec<usize> {
+        vec![1]
+    }
+
+    fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+        vec![
+            BoundaryAssertion {
+                column: 0,
+                row: 0,
+                value: self.public_inputs.first.clone(),
+            },
+            BoundaryAssertion {
+                column: 0,
+                row: 1,
+                value: self.public_inputs.second.clone(),
+            },
+            BoundaryAssertion {
+                column: 0,
+                row: self.trace_length - 1,
+                value: self.public_inputs.result.clone(),
+            },
+        ]
+    }
+}
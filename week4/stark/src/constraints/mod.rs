@@ -0,0 +1,299 @@
+pub mod check;
+pub mod evaluator;
+pub mod fibonacci;
+pub mod zerofier;
+
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+};
+
+use crate::trace::TraceTable;
+
+use self::zerofier::TransitionZerofier;
+
+pub struct BoundaryAssertion<F: IsField> {
+    pub column: usize,
+    pub row: usize,
+    pub value: FieldElement<F>,
+}
+
+// trace rows at the air transition offsets, `get_row(i)` is the row at `transition_offsets()[i]`
+pub struct Frame<F: IsField> {
+    rows: Vec<Vec<FieldElement<F>>>,
+}
+
+impl<F: IsField> Frame<F> {
+    pub fn new(rows: Vec<Vec<FieldElement<F>>>) -> Self {
+        Self { rows }
+    }
+
+    pub fn get_row(&self, step: usize) -> &[FieldElement<F>] {
+        &self.rows[step]
+    }
+
+    pub fn rows(&self) -> &[Vec<FieldElement<F>>] {
+        &self.rows
+    }
+
+    pub fn read_from_trace(trace: &TraceTable<F>, row: usize, offsets: &[usize]) -> Self {
+        Self::new(
+            offsets
+                .iter()
+                .map(|offset| trace.row((row + offset) % trace.n_rows()).to_vec())
+                .collect(),
+        )
+    }
+
+    pub fn zeroed(n_rows: usize, width: usize) -> Self {
+        Self::new(vec![vec![FieldElement::zero(); width]; n_rows])
+    }
+
+    // refills the frame in place from the trace lde columns, on which g^k x sits
+    // `blowup_factor * k` positions after x
+    pub fn fill_from_lde(
+        &mut self,
+        lde_columns: &[Vec<FieldElement<F>>],
+        index: usize,
+        offsets: &[usize],
+        blowup_factor: usize,
+    ) {
+        for (row, offset) in self.rows.iter_mut().zip(offsets) {
+            for (value, column) in row.iter_mut().zip(lde_columns) {
+                *value = column[(index + offset * blowup_factor) % column.len()].clone();
+            }
+        }
+    }
+
+    pub fn read_from_polys(
+        trace_polys: &[Polynomial<FieldElement<F>>],
+        evaluation_point: &FieldElement<F>,
+        root_of_unity: &FieldElement<F>,
+        offsets: &[usize],
+    ) -> Self {
+        Self::new(
+            offsets
+                .iter()
+                .map(|offset| {
+                    let point = root_of_unity.pow(*offset) * evaluation_point;
+                    trace_polys
+                        .iter()
+                        .map(|poly| poly.evaluate(&point))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+pub trait Air<F: IsFFTField> {
+    fn trace_length(&self) -> usize;
+
+    fn trace_width(&self) -> usize;
+
+    // row offsets read by the transition constraints, [0, 1, 2] reads x, gx and g^2x,
+    // the first offset must be 0 as boundary constraints read the current row, which
+    // `check::check_air` enforces
+    fn transition_offsets(&self) -> Vec<usize>;
+
+    // number of final rows on which the transition constraints are not enforced, only read
+    // by the default `transition_exempted_rows`
+    fn transition_exemptions(&self) -> usize {
+        0
+    }
+
+    // the transition constraints apply to rows 0, s, 2s, ... for a step s dividing the
+    // trace length
+    fn transition_step(&self) -> usize {
+        1
+    }
+
+    // rows, among those the transition constraints apply to, on which they are not enforced;
+    // the last `transition_exemptions()` of them unless overridden, in which case
+    // `transition_exemptions` is left out
+    fn transition_exempted_rows(&self) -> Vec<usize> {
+        (1..=self.transition_exemptions())
+            .map(|k| self.trace_length() - k * self.transition_step())
+            .collect()
+    }
+
+    fn transition_zerofier(&self) -> TransitionZerofier<F> {
+        TransitionZerofier::new(
+            self.trace_length(),
+            self.transition_step(),
+            &self.transition_exempted_rows(),
+        )
+    }
+
+    // `periodic_values` holds the value of every periodic column at the frame's first row
+    fn evaluate_transition(
+        &self,
+        frame: &Frame<F>,
+        periodic_values: &[FieldElement<F>],
+    ) -> Vec<FieldElement<F>>;
+
+    // degree of every transition constraint in the trace columns, periodic columns counting
+    // as trace columns
+    fn transition_degrees(&self) -> Vec<usize>;
+
+    // assertions derived from the public inputs, bound to the proof through the transcript
+    fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>>;
+
+    // columns fixed by the air, such as round constants, given by their values over one
+    // period; the period must be a power of two dividing the trace length
+    fn periodic_columns(&self) -> Vec<Vec<FieldElement<F>>> {
+        vec![]
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.boundary_assertions().len() + self.transition_degrees().len()
+    }
+
+    fn trace_generator(&self) -> FieldElement<F> {
+        F::get_primitive_root_of_unity(self.trace_length().trailing_zeros() as u64).unwrap()
+    }
+}
+
+// a periodic column with period p is P(x^(n/p)), with P interpolating its values over the
+// p-th roots of unity, so the verifier evaluates it in O(p) whatever the trace length
+pub fn periodic_polys<F, A>(air: &A) -> Vec<Polynomial<FieldElement<F>>>
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    air.periodic_columns()
+        .iter()
+        .map(|values| {
+            assert!(
+                values.len().is_power_of_two() && air.trace_length().is_multiple_of(values.len()),
+                "periodic column period must be a power of two dividing the trace length"
+            );
+            Polynomial::interpolate_fft::<F>(values).unwrap()
+        })
+        .collect()
+}
+
+pub fn eval_periodic_columns<F, A>(
+    air: &A,
+    evaluation_point: &FieldElement<F>,
+) -> Vec<FieldElement<F>>
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    air.periodic_columns()
+        .iter()
+        .zip(periodic_polys(air))
+        .map(|(values, poly)| {
+            poly.evaluate(&evaluation_point.pow(air.trace_length() / values.len()))
+        })
+        .collect()
+}
+
+// degree of every composition term, boundary assertions first, for trace polynomials of
+// degree < `trace_degree_bound`: a boundary quotient (t(x) - v) / (x - g^row) loses one degree,
+// a transition constraint of degree d reaches d (D - 1) before the zerofier divides it
+pub fn constraint_degrees<F, A>(air: &A, trace_degree_bound: usize) -> Vec<usize>
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    let zerofier_degree = air.transition_zerofier().degree();
+    let boundary = vec![trace_degree_bound.saturating_sub(2); air.boundary_assertions().len()];
+    let transition = air
+        .transition_degrees()
+        .into_iter()
+        .map(|degree| (degree * (trace_degree_bound - 1)).saturating_sub(zerofier_degree));
+    boundary.into_iter().chain(transition).collect()
+}
+
+pub fn composition_degree<F, A>(air: &A, trace_degree_bound: usize) -> usize
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    constraint_degrees(air, trace_degree_bound)
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+}
+
+// H(x) = H_0(x^k) + x H_1(x^k) + ... + x^(k-1) H_(k-1)(x^k) with k the fewest parts that keep
+// every H_i within the trace degree bound fri is run against
+pub fn composition_parts<F, A>(air: &A, trace_degree_bound: usize) -> usize
+where
+    F: IsFFTField,
+    A: Air<F>,
+{
+    composition_degree(air, trace_degree_bound) / trace_degree_bound + 1
+}
+
+// alphas hold one coefficient per boundary assertion followed by one per transition constraint,
+// `frame` holds the trace rows at the air offsets from `evaluation_point`
+pub fn eval_composition_polynomial<F, A>(
+    air: &A,
+    frame: &Frame<F>,
+    evaluation_point: &FieldElement<F>,
+    alphas: &[FieldElement<F>],
+) -> FieldElement<F>
+where
+    F: IsField + IsFFTField,
+    A: Air<F>,
+{
+    let (boundary_alphas, transition_alphas) = alphas.split_at(air.boundary_assertions().len());
+
+    eval_boundary_constraints(air, frame, evaluation_point)
+        .iter()
+        .zip(boundary_alphas)
+        .chain(
+            eval_transition_constraints(air, frame, evaluation_point)
+                .iter()
+                .zip(transition_alphas),
+        )
+        .fold(FieldElement::zero(), |acc, (constraint, alpha)| {
+            acc + constraint * alpha
+        })
+}
+
+pub fn eval_boundary_constraints<F, A>(
+    air: &A,
+    frame: &Frame<F>,
+    evaluation_point: &FieldElement<F>,
+) -> Vec<FieldElement<F>>
+where
+    F: IsField + IsFFTField,
+    A: Air<F>,
+{
+    let root_of_unity = air.trace_generator();
+
+    air.boundary_assertions()
+        .iter()
+        .map(|assertion| {
+            let eval_at_point = &frame.get_row(0)[assertion.column];
+            let inv_at_row = (evaluation_point - root_of_unity.pow(assertion.row))
+                .inv()
+                .expect("Inversion at boundary row failed");
+            (eval_at_point - &assertion.value) * inv_at_row
+        })
+        .collect()
+}
+
+pub fn eval_transition_constraints<F, A>(
+    air: &A,
+    frame: &Frame<F>,
+    evaluation_point: &FieldElement<F>,
+) -> Vec<FieldElement<F>>
+where
+    F: IsField + IsFFTField,
+    A: Air<F>,
+{
+    let zerofier_inv = air.transition_zerofier().evaluate_inverse(evaluation_point);
+
+    air.evaluate_transition(frame, &eval_periodic_columns(air, evaluation_point))
+        .into_iter()
+        .map(|constraint| constraint * &zerofier_inv)
+        .collect()
+}
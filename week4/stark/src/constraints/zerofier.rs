@@ -0,0 +1,176 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::fri::Coset;
+
+use super::evaluator::batch_inverse;
+
+// Z(x) = (x^(n/s) - 1) / prod_e (x - g^e), vanishing on every s-th row of the trace domain
+// but the exempted ones, so a transition constraint divided by it is a polynomial exactly
+// when it holds on those rows
+pub struct TransitionZerofier<F: IsFFTField> {
+    trace_length: usize,
+    step: usize,
+    exempted_points: Vec<FieldElement<F>>,
+}
+
+impl<F: IsFFTField> TransitionZerofier<F> {
+    pub fn new(trace_length: usize, step: usize, exempted_rows: &[usize]) -> Self {
+        assert!(
+            step.is_power_of_two() && trace_length.is_multiple_of(step),
+            "transition step must be a power of two dividing the trace length"
+        );
+        let mut rows = exempted_rows.to_vec();
+        rows.sort_unstable();
+        rows.dedup();
+        assert!(
+            rows.len() == exempted_rows.len()
+                && rows
+                    .iter()
+                    .all(|row| *row < trace_length && row.is_multiple_of(step)),
+            "exempted rows must be distinct rows the transition constraints apply to"
+        );
+
+        let trace_generator =
+            F::get_primitive_root_of_unity(trace_length.trailing_zeros() as u64).unwrap();
+        TransitionZerofier {
+            trace_length,
+            step,
+            exempted_points: exempted_rows
+                .iter()
+                .map(|row| trace_generator.pow(*row))
+                .collect(),
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.trace_length / self.step - self.exempted_points.len()
+    }
+
+    fn vanishing_degree(&self) -> usize {
+        self.trace_length / self.step
+    }
+
+    fn exemptions(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        self.exempted_points
+            .iter()
+            .fold(FieldElement::one(), |acc, point| acc * (x - point))
+    }
+
+    // `x` must lie outside the rows the constraints apply to, exempted rows included
+    pub fn evaluate(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        (x.pow(self.vanishing_degree()) - FieldElement::<F>::one())
+            * self
+                .exemptions(x)
+                .inv()
+                .expect("Zerofier evaluated at an exempted row")
+    }
+
+    pub fn evaluate_inverse(&self, x: &FieldElement<F>) -> FieldElement<F> {
+        self.exemptions(x)
+            * (x.pow(self.vanishing_degree()) - FieldElement::<F>::one())
+                .inv()
+                .expect("Zerofier evaluated on a constrained row")
+    }
+
+    // 1 / Z over a coset of the trace domain: (offset w^i)^(n/s) repeats every size * s / n
+    // points, so only that many numerators are inverted, in a single batch
+    pub fn inverse_evaluations_over_coset(&self, domain: &Coset<F>) -> Vec<FieldElement<F>>
+    where
+        FieldElement<F>: Send + Sync,
+    {
+        let vanishing_degree = self.vanishing_degree();
+        assert_eq!(domain.size % vanishing_degree, 0);
+        let period = domain.size / vanishing_degree;
+
+        let numerator_step = domain.generator.pow(vanishing_degree);
+        let mut numerators = Vec::with_capacity(period);
+        let mut power = domain.offset.pow(vanishing_degree);
+        for _ in 0..period {
+            numerators.push(&power - FieldElement::<F>::one());
+            power *= &numerator_step;
+        }
+        let numerator_inv = batch_inverse(&numerators);
+
+        into_iter!(0..domain.size)
+            .map(|i| self.exemptions(&domain.element(i)) * &numerator_inv[i % period])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    // prod (x - g^i) over the rows i = 0, s, 2s, ... that are not exempted
+    fn naive_zerofier(x: &FE, trace_length: usize, step: usize, exempted_rows: &[usize]) -> FE {
+        let g = F::get_primitive_root_of_unity(trace_length.trailing_zeros() as u64).unwrap();
+        (0..trace_length)
+            .step_by(step)
+            .filter(|row| !exempted_rows.contains(row))
+            .fold(FE::one(), |acc, row| acc * (x - g.pow(row)))
+    }
+
+    const CASES: [(usize, usize, &[usize]); 4] = [
+        (16, 1, &[]),
+        (16, 1, &[15, 14]),
+        (32, 4, &[28]),
+        (32, 2, &[0, 10, 30]),
+    ];
+
+    #[test]
+    fn zerofier_matches_naive_product() {
+        for (trace_length, step, exempted_rows) in CASES {
+            let zerofier = TransitionZerofier::<F>::new(trace_length, step, exempted_rows);
+            for x in [3, 7, 1234567].map(FE::from) {
+                let expected = naive_zerofier(&x, trace_length, step, exempted_rows);
+                assert_eq!(zerofier.evaluate(&x), expected);
+                assert_eq!(zerofier.evaluate_inverse(&x), expected.inv().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn zerofier_vanishes_exactly_on_constrained_rows() {
+        for (trace_length, step, exempted_rows) in CASES {
+            let zerofier = TransitionZerofier::<F>::new(trace_length, step, exempted_rows);
+            let g = F::get_primitive_root_of_unity(trace_length.trailing_zeros() as u64).unwrap();
+            let mut roots = 0;
+            for row in (0..trace_length).filter(|row| !exempted_rows.contains(row)) {
+                let vanishes = zerofier.evaluate(&g.pow(row)) == FE::zero();
+                assert_eq!(vanishes, row.is_multiple_of(step));
+                roots += vanishes as usize;
+            }
+            // as many roots as its degree already, so none is left for an exempted row
+            assert_eq!(roots, zerofier.degree());
+        }
+    }
+
+    #[test]
+    fn coset_inverses_match_pointwise_evaluation() {
+        for (trace_length, step, exempted_rows) in CASES {
+            let zerofier = TransitionZerofier::<F>::new(trace_length, step, exempted_rows);
+            let size = trace_length * 4;
+            let domain = Coset {
+                generator: F::get_primitive_root_of_unity(size.trailing_zeros() as u64).unwrap(),
+                offset: FE::from(3),
+                size,
+            };
+            let inverses = zerofier.inverse_evaluations_over_coset(&domain);
+
+            assert_eq!(inverses.len(), size);
+            for (i, inverse) in inverses.iter().enumerate() {
+                let expected =
+                    naive_zerofier(&domain.element(i), trace_length, step, exempted_rows);
+                assert_eq!(*inverse, expected.inv().unwrap());
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    constraints::{Air, Frame},
+    fri::Coset,
+    transcript::Transcript,
+};
+
+// z must avoid the trace domain, where the constraint quotients are undefined, and every
+// shift g^k z as well as z^parts must avoid the lde coset, where the DEEP quotients are undefined
+pub fn sample_ood_point<F: IsFFTField>(
+    transcript: &mut Transcript,
+    trace_length: usize,
+    composition_parts: usize,
+    lde_domain: &Coset<F>,
+) -> FieldElement<F> {
+    let one = FieldElement::<F>::one();
+    let offset_inv = lde_domain.offset.inv().unwrap();
+    loop {
+        let z: FieldElement<F> = transcript.sample_field_element();
+        if z.pow(trace_length) != one
+            && (&z * &offset_inv).pow(lde_domain.size) != one
+            && (z.pow(composition_parts) * &offset_inv).pow(lde_domain.size) != one
+        {
+            return z;
+        }
+    }
+}
+
+pub fn append_ood_evaluations<F: IsField>(
+    transcript: &mut Transcript,
+    ood_frame: &Frame<F>,
+    ood_composition: &[FieldElement<F>],
+) where
+    FieldElement<F>: AsBytes,
+{
+    for value in ood_frame.rows().iter().flatten().chain(ood_composition) {
+        transcript.append_field_element(value);
+    }
+}
+
+// one coefficient per cell of the ood frame and one per composition column, the composition
+// parts followed by the masking polynomial in zero knowledge mode
+pub fn number_of_deep_coefficients<F: IsFFTField, A: Air<F>>(
+    air: &A,
+    composition_columns: usize,
+) -> usize {
+    air.transition_offsets().len() * air.trace_width() + composition_columns
+}
+
+// where the DEEP quotients open the committed polynomials: the trace at g^k z for every
+// transition offset k, the composition columns at z^parts since H(z) = sum_i z^i H_i(z^parts)
+pub struct OodPoints<F: IsField> {
+    pub trace: Vec<FieldElement<F>>,
+    pub composition: FieldElement<F>,
+}
+
+pub fn ood_points<F: IsField>(
+    z: &FieldElement<F>,
+    trace_generator: &FieldElement<F>,
+    offsets: &[usize],
+    composition_parts: usize,
+) -> OodPoints<F> {
+    OodPoints {
+        trace: offsets
+            .iter()
+            .map(|offset| trace_generator.pow(*offset) * z)
+            .collect(),
+        composition: z.pow(composition_parts),
+    }
+}
+
+// D(x) = sum_k,j gamma_k,j (t_j(x) - t_j(g^k z)) / (x - g^k z)
+//      + sum_i gamma_i (H_i(x) - H_i(z^parts)) / (x - z^parts)
+// with gammas ordered row by row of the ood frame and the composition coefficients last
+pub fn deep_composition_poly<F: IsField>(
+    trace_polys: &[Polynomial<FieldElement<F>>],
+    composition_polys: &[Polynomial<FieldElement<F>>],
+    ood_points: &OodPoints<F>,
+    ood_frame: &Frame<F>,
+    ood_composition: &[FieldElement<F>],
+    gammas: &[FieldElement<F>],
+) -> Polynomial<FieldElement<F>> {
+    let mut gammas = gammas.iter();
+    let mut deep_poly = Polynomial::zero();
+
+    for (step, point) in ood_points.trace.iter().enumerate() {
+        for (poly, value) in trace_polys.iter().zip(ood_frame.get_row(step)) {
+            let quotient = (poly - value).ruffini_division(point);
+            deep_poly = deep_poly + quotient * gammas.next().unwrap();
+        }
+    }
+
+    for (poly, value) in composition_polys.iter().zip(ood_composition) {
+        let quotient = (poly - value).ruffini_division(&ood_points.composition);
+        deep_poly = deep_poly + quotient * gammas.next().unwrap();
+    }
+    deep_poly
+}
+
+// evaluation of the DEEP composition polynomial at `x` from opened trace and composition values
+pub fn eval_deep_composition<F: IsField>(
+    x: &FieldElement<F>,
+    trace_values: &[FieldElement<F>],
+    composition_values: &[FieldElement<F>],
+    ood_points: &OodPoints<F>,
+    ood_frame: &Frame<F>,
+    ood_composition: &[FieldElement<F>],
+    gammas: &[FieldElement<F>],
+) -> FieldElement<F> {
+    let mut gammas = gammas.iter();
+    let mut deep_value = FieldElement::<F>::zero();
+
+    for (step, point) in ood_points.trace.iter().enumerate() {
+        let denominator_inv = (x - point).inv().unwrap();
+        for (value, ood_value) in trace_values.iter().zip(ood_frame.get_row(step)) {
+            deep_value += gammas.next().unwrap() * (value - ood_value) * &denominator_inv;
+        }
+    }
+
+    let denominator_inv = (x - &ood_points.composition).inv().unwrap();
+    for (value, ood_value) in composition_values.iter().zip(ood_composition) {
+        deep_value += gammas.next().unwrap() * (value - ood_value) * &denominator_inv;
+    }
+    deep_value
+}
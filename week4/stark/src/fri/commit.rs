@@ -0,0 +1,155 @@
+use lambdaworks_crypto::merkle_tree::merkle::MerkleTree;
+use lambdaworks_math::field::traits::IsFFTField;
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    merkle::{backends::CommitmentBackend, MultiProof},
+    proof::options::ProofOptions,
+    transcript::Transcript,
+    zk::{leaf_salts, salted_leaf},
+};
+
+use super::{next_fri_layer, Coset};
+
+// leaf j of a layer tree holds the coset x_j * <omega> folded together into one point of the
+// next layer, and the paths of all queries are merged in a single multiproof;
+// in zero knowledge mode every leaf is salted and the salts of the opened leaves are sent along
+pub struct LayerCommitment<F: IsField> {
+    pub merkle_root: [u8; 32],
+    pub domain_size: usize,
+    pub inclusion_proof: MultiProof<[u8; 32]>,
+    pub cosets: Vec<Vec<FieldElement<F>>>,
+    pub salts: Vec<FieldElement<F>>,
+}
+
+// with n/arity leaves, leaf j is [f(w^j), f(w^(j + n/arity)), f(w^(j + 2n/arity)), ...]
+fn coset_leaves<F>(evals: &[FieldElement<F>], arity: usize) -> Vec<Vec<FieldElement<F>>>
+where
+    F: IsField,
+    FieldElement<F>: Send + Sync,
+{
+    let leaves = evals.len() / arity;
+    into_iter!(0..leaves)
+        .map(|j| (0..arity).map(|t| evals[j + t * leaves].clone()).collect())
+        .collect()
+}
+
+impl<F: IsField> LayerCommitment<F> {
+    // value of the layer at the query position, read from the opened coset
+    pub fn query_evaluation(&self, n: usize, query: usize) -> &FieldElement<F> {
+        let leaves = self.domain_size / self.cosets[n].len();
+        &self.cosets[n][(query % self.domain_size) / leaves]
+    }
+}
+
+impl<F> LayerCommitment<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    fn new<B: CommitmentBackend<F>>(
+        tree: &MerkleTree<B>,
+        leaves: &[Vec<FieldElement<F>>],
+        salts: &[FieldElement<F>],
+        domain_size: usize,
+        queries: &[usize],
+    ) -> Self {
+        let positions: Vec<usize> = queries.iter().map(|q| q % leaves.len()).collect();
+        LayerCommitment {
+            merkle_root: tree.root,
+            domain_size,
+            inclusion_proof: MultiProof::build(tree, &positions),
+            cosets: positions
+                .iter()
+                .map(|position| leaves[*position].clone())
+                .collect(),
+            salts: positions
+                .iter()
+                .filter_map(|position| salts.get(*position).cloned())
+                .collect(),
+        }
+    }
+}
+
+pub struct FriCommitment<F: IsField> {
+    pub layers: Vec<LayerCommitment<F>>,
+    pub last_layer_poly: Polynomial<FieldElement<F>>,
+    pub queries: Vec<usize>,
+    pub nonce: u64,
+}
+
+// commits to `number_of_folds` layers, layer i evaluated on the image of `domain` under
+// x -> x^(folding_factor^i), and folds once more into the last layer, which is sent as plain
+// coefficients instead of a merkle tree,
+// drawing every folding beta from the transcript right after the layer root and the queries
+// only once every layer is fixed and the proof of work nonce is absorbed
+pub fn commit<F, B, R>(
+    poly: &Polynomial<FieldElement<F>>,
+    domain: &Coset<F>,
+    number_of_folds: usize,
+    options: &ProofOptions,
+    transcript: &mut Transcript,
+    rng: &mut R,
+) -> FriCommitment<F>
+where
+    F: IsField + IsFFTField,
+    B: CommitmentBackend<F>,
+    R: Rng,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    let folding_factor = options.fri_folding_factor;
+    let mut committed = vec![];
+    let mut curr_poly = poly.clone();
+    let mut curr_domain = domain.clone();
+
+    for _ in 0..number_of_folds {
+        let evals = Polynomial::evaluate_offset_fft::<F>(
+            &curr_poly,
+            1,
+            Some(curr_domain.size),
+            &curr_domain.offset,
+        )
+        .unwrap();
+
+        let leaves = coset_leaves(&evals, folding_factor);
+        let salts = leaf_salts(options.zero_knowledge, leaves.len(), rng);
+        let salted_leaves: Vec<Vec<FieldElement<F>>> = iter!(&leaves)
+            .enumerate()
+            .map(|(j, leaf)| salted_leaf(leaf, salts.get(j)))
+            .collect();
+        let tree = MerkleTree::<B>::build(&salted_leaves);
+        transcript.append_bytes(&tree.root);
+        committed.push((tree, leaves, salts, curr_domain.size));
+
+        let beta = transcript.sample_field_element();
+        (curr_poly, curr_domain) = next_fri_layer(&curr_poly, &beta, &curr_domain, folding_factor);
+    }
+
+    for coefficient in curr_poly.coefficients() {
+        transcript.append_field_element(coefficient);
+    }
+    let nonce = transcript.grind(options.grinding_bits);
+    transcript.append_bytes(&nonce.to_be_bytes());
+    let queries = transcript.sample_indices(options.number_of_queries, domain.size);
+
+    let layers = iter!(&committed)
+        .map(|(tree, leaves, salts, size)| {
+            LayerCommitment::new(tree, leaves, salts, *size, &queries)
+        })
+        .collect();
+
+    FriCommitment {
+        layers,
+        last_layer_poly: curr_poly,
+        queries,
+        nonce,
+    }
+}
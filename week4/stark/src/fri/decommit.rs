@@ -0,0 +1,309 @@
+use std::fmt;
+
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsPrimeField},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    merkle::backends::CommitmentBackend, proof::options::ProofOptions, transcript::Transcript,
+    zk::salted_leaf,
+};
+
+use super::{commit::LayerCommitment, Coset};
+
+// replays the prover transcript over the received layer roots to recover betas and queries,
+// checking the proof of work nonce on the way
+pub fn replay_challenges<F>(
+    layers: &[LayerCommitment<F>],
+    last_layer_poly: &Polynomial<FieldElement<F>>,
+    domain_size: usize,
+    number_of_queries: usize,
+    grinding_bits: usize,
+    nonce: u64,
+    transcript: &mut Transcript,
+) -> Result<(Vec<FieldElement<F>>, Vec<usize>), FriVerifyError>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes,
+{
+    let mut betas = vec![];
+    for layer in layers {
+        transcript.append_bytes(&layer.merkle_root);
+        betas.push(transcript.sample_field_element());
+    }
+
+    for coefficient in last_layer_poly.coefficients() {
+        transcript.append_field_element(coefficient);
+    }
+    if !transcript.is_valid_nonce(nonce, grinding_bits) {
+        return Err(FriVerifyError::InvalidProofOfWork);
+    }
+    transcript.append_bytes(&nonce.to_be_bytes());
+    let queries = transcript.sample_indices(number_of_queries, domain_size);
+
+    Ok((betas, queries))
+}
+
+// the values of f on the coset x * <omega> of size k are those of the interpolant
+// P(X) = sum_j X^j f_j(x^k), so the folded value sum_j beta^j f_j(x^k) is P(beta); the
+// coefficients of P come from an inverse DFT of the values scaled by x^-j
+pub fn fold_coset<F: IsField>(
+    values: &[FieldElement<F>],
+    x: &FieldElement<F>,
+    omega_inv: &FieldElement<F>,
+    beta: &FieldElement<F>,
+) -> FieldElement<F> {
+    let beta_over_x = beta * x.inv().unwrap();
+    let mut folded = FieldElement::<F>::zero();
+    let mut beta_over_x_power = FieldElement::<F>::one();
+    for j in 0..values.len() {
+        let omega_inv_j = omega_inv.pow(j);
+        let mut root = FieldElement::<F>::one();
+        let mut coefficient = FieldElement::<F>::zero();
+        for value in values {
+            coefficient += value * &root;
+            root *= &omega_inv_j;
+        }
+        folded += coefficient * &beta_over_x_power;
+        beta_over_x_power *= &beta_over_x;
+    }
+    folded * FieldElement::<F>::from(values.len() as u64).inv().unwrap()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FriVerifyError {
+    LayerCountMismatch { expected: usize, found: usize },
+    DomainSizeMismatch { layer: usize },
+    QueryCountMismatch { layer: usize },
+    MerklePathInvalid { layer: usize },
+    FoldingMismatch { layer: usize, query: usize },
+    LastLayerDegreeTooHigh { degree: usize, bound: usize },
+    LastLayerMismatch { query: usize },
+    InvalidProofOfWork,
+}
+
+impl fmt::Display for FriVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FriVerifyError::LayerCountMismatch { expected, found } => {
+                write!(f, "expected {expected} fri layers, found {found}")
+            }
+            FriVerifyError::DomainSizeMismatch { layer } => {
+                write!(f, "unexpected domain size in layer {layer}")
+            }
+            FriVerifyError::QueryCountMismatch { layer } => {
+                write!(f, "wrong number of query openings in layer {layer}")
+            }
+            FriVerifyError::MerklePathInvalid { layer } => {
+                write!(f, "invalid merkle multiproof in layer {layer}")
+            }
+            FriVerifyError::FoldingMismatch { layer, query } => {
+                write!(f, "folding mismatch in layer {layer} for query {query}")
+            }
+            FriVerifyError::LastLayerDegreeTooHigh { degree, bound } => {
+                write!(f, "last layer degree {degree} exceeds bound {bound}")
+            }
+            FriVerifyError::LastLayerMismatch { query } => {
+                write!(f, "last layer polynomial mismatch for query {query}")
+            }
+            FriVerifyError::InvalidProofOfWork => write!(f, "invalid proof of work nonce"),
+        }
+    }
+}
+
+impl std::error::Error for FriVerifyError {}
+
+pub fn verify_fri<F, B>(
+    layers: &[LayerCommitment<F>],
+    last_layer_poly: &Polynomial<FieldElement<F>>,
+    betas: &[FieldElement<F>],
+    queries: &[usize],
+    domain: &Coset<F>,
+    options: &ProofOptions,
+) -> Result<(), FriVerifyError>
+where
+    F: IsField + IsFFTField + IsPrimeField,
+    B: CommitmentBackend<F>,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    let folding_factor = options.fri_folding_factor;
+    let last_layer_degree_bound =
+        options.fri_last_layer_size(domain.size / options.blowup_factor) - 1;
+    let salts_per_layer = if options.zero_knowledge {
+        queries.len()
+    } else {
+        0
+    };
+
+    if last_layer_poly.degree() > last_layer_degree_bound {
+        return Err(FriVerifyError::LastLayerDegreeTooHigh {
+            degree: last_layer_poly.degree(),
+            bound: last_layer_degree_bound,
+        });
+    }
+
+    let mut folded_evals: Vec<FieldElement<F>> = vec![];
+    let mut curr_domain = domain.clone();
+
+    for (i, curr_layer) in layers.iter().enumerate() {
+        let curr_domain_size = curr_domain.size;
+        if curr_layer.domain_size != curr_domain_size {
+            return Err(FriVerifyError::DomainSizeMismatch { layer: i });
+        }
+        if curr_layer.cosets.len() != queries.len()
+            || curr_layer
+                .cosets
+                .iter()
+                .any(|coset| coset.len() != folding_factor)
+            || curr_layer.salts.len() != salts_per_layer
+        {
+            return Err(FriVerifyError::QueryCountMismatch { layer: i });
+        }
+
+        // omega generates the cosets x * <omega> held by every leaf
+        let leaves_count = curr_domain_size / folding_factor;
+        let omega_inv = curr_domain.generator.pow(leaves_count).inv().unwrap();
+        let mut leaves = vec![];
+        let mut next_layer_evals = vec![];
+        for (n, query) in queries.iter().enumerate() {
+            if i > 0 && folded_evals[n] != *curr_layer.query_evaluation(n, *query) {
+                return Err(FriVerifyError::FoldingMismatch { layer: i, query: n });
+            }
+
+            let leaf = query % leaves_count;
+            leaves.push((
+                leaf,
+                salted_leaf(&curr_layer.cosets[n], curr_layer.salts.get(n)),
+            ));
+
+            next_layer_evals.push(fold_coset(
+                &curr_layer.cosets[n],
+                &curr_domain.element(leaf),
+                &omega_inv,
+                &betas[i],
+            ));
+        }
+
+        if !curr_layer.inclusion_proof.verify::<B>(
+            &curr_layer.merkle_root,
+            leaves_count.trailing_zeros() as usize,
+            &leaves,
+        ) {
+            return Err(FriVerifyError::MerklePathInvalid { layer: i });
+        }
+
+        folded_evals = next_layer_evals;
+        curr_domain = curr_domain.fold(folding_factor);
+    }
+
+    // the last fold lands on the coefficients sent in the clear
+    for (n, query) in queries.iter().enumerate() {
+        let x = curr_domain.element(query % curr_domain.size);
+        if last_layer_poly.evaluate(&x) != folded_evals[n] {
+            return Err(FriVerifyError::LastLayerMismatch { query: n });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+    use crate::{
+        fri::{commit::commit, fold_polynomial},
+        merkle::backends::KeccakBackend,
+    };
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    #[test]
+    fn coset_fold_matches_polynomial_fold() {
+        let poly = Polynomial::new(&(1..=32).map(FE::from).collect::<Vec<FE>>());
+        let beta = FE::from(11);
+        let size: usize = 64;
+        let domain = Coset {
+            generator: F::get_primitive_root_of_unity(size.trailing_zeros() as u64).unwrap(),
+            offset: FE::from(3),
+            size,
+        };
+
+        for arity in [2, 4, 8] {
+            let folded = fold_polynomial(&poly, &beta, arity);
+            let leaves_count = size / arity;
+            let omega = domain.generator.pow(leaves_count);
+            for leaf in [0, 1, leaves_count - 1] {
+                let x = domain.element(leaf);
+                let values: Vec<FE> = (0..arity)
+                    .map(|j| poly.evaluate(&(x * omega.pow(j))))
+                    .collect();
+
+                assert_eq!(
+                    fold_coset(&values, &x, &omega.inv().unwrap(), &beta),
+                    folded.evaluate(&x.pow(arity))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn last_layer_is_checked_against_the_folded_queries() {
+        let options = ProofOptions {
+            blowup_factor: 8,
+            number_of_queries: 8,
+            ..ProofOptions::default()
+        };
+        let trace_length = 32;
+        let domain = options.lde_domain::<F>(trace_length);
+        let number_of_folds = options.fri_number_of_folds(trace_length);
+        let poly = Polynomial::new(&(1..=32).map(FE::from).collect::<Vec<FE>>());
+
+        let mut transcript = Transcript::new(b"fri");
+        let commitment = commit::<F, KeccakBackend<F>, _>(
+            &poly,
+            &domain,
+            number_of_folds,
+            &options,
+            &mut transcript,
+            &mut rand::thread_rng(),
+        );
+        let (betas, queries) = replay_challenges(
+            &commitment.layers,
+            &commitment.last_layer_poly,
+            domain.size,
+            options.number_of_queries,
+            options.grinding_bits,
+            commitment.nonce,
+            &mut Transcript::new(b"fri"),
+        )
+        .unwrap();
+        assert_eq!(queries, commitment.queries);
+        let verify = |last_layer_poly: &Polynomial<FE>| {
+            verify_fri::<F, KeccakBackend<F>>(
+                &commitment.layers,
+                last_layer_poly,
+                &betas,
+                &queries,
+                &domain,
+                &options,
+            )
+        };
+        assert_eq!(verify(&commitment.last_layer_poly), Ok(()));
+
+        // with the challenges held fixed, only the last layer evaluation can catch it
+        let mut coefficients = commitment.last_layer_poly.coefficients().to_vec();
+        coefficients[0] += FE::one();
+        assert_eq!(
+            verify(&Polynomial::new(&coefficients)),
+            Err(FriVerifyError::LastLayerMismatch { query: 0 })
+        );
+    }
+}
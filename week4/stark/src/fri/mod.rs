@@ -0,0 +1,68 @@
+pub mod commit;
+pub mod decommit;
+
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+};
+
+// evaluation domain offset * <generator> with `size` elements
+#[derive(Clone, Debug)]
+pub struct Coset<F: IsField> {
+    pub generator: FieldElement<F>,
+    pub offset: FieldElement<F>,
+    pub size: usize,
+}
+
+impl<F: IsField> Coset<F> {
+    pub fn element(&self, index: usize) -> FieldElement<F> {
+        &self.offset * self.generator.pow(index)
+    }
+
+    // image of the coset under x -> x^arity
+    pub fn fold(&self, arity: usize) -> Self {
+        Coset {
+            generator: self.generator.pow(arity),
+            offset: self.offset.pow(arity),
+            size: self.size / arity,
+        }
+    }
+}
+
+// f(x) = sum_j x^j f_j(x^arity) folds into sum_j beta^j f_j(y)
+pub fn fold_polynomial<F>(
+    poly: &Polynomial<FieldElement<F>>,
+    beta: &FieldElement<F>,
+    arity: usize,
+) -> Polynomial<FieldElement<F>>
+where
+    F: IsField,
+{
+    let beta_powers: Vec<FieldElement<F>> =
+        std::iter::successors(Some(FieldElement::one()), |power| Some(power * beta))
+            .take(arity)
+            .collect();
+
+    let folded: Vec<FieldElement<F>> = poly
+        .coefficients()
+        .chunks(arity)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .zip(&beta_powers)
+                .fold(FieldElement::zero(), |acc, (coefficient, power)| {
+                    acc + coefficient * power
+                })
+        })
+        .collect();
+    Polynomial::new(&folded)
+}
+
+pub fn next_fri_layer<F: IsField>(
+    poly: &Polynomial<FieldElement<F>>,
+    beta: &FieldElement<F>,
+    domain: &Coset<F>,
+    arity: usize,
+) -> (Polynomial<FieldElement<F>>, Coset<F>) {
+    (fold_polynomial(poly, beta, arity), domain.fold(arity))
+}
@@ -1,14 +1,1065 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+// rayon iterators with the `parallel` feature and std ones otherwise, both yield the items in
+// the same order so the proof bytes do not depend on the feature
+macro_rules! iter {
+    ($e:expr) => {{
+        #[cfg(feature = "parallel")]
+        let iter = rayon::iter::IntoParallelRefIterator::par_iter($e);
+        #[cfg(not(feature = "parallel"))]
+        let iter = $e.iter();
+        iter
+    }};
 }
 
+macro_rules! into_iter {
+    ($e:expr) => {{
+        #[cfg(feature = "parallel")]
+        let iter = rayon::iter::IntoParallelIterator::into_par_iter($e);
+        #[cfg(not(feature = "parallel"))]
+        let iter = IntoIterator::into_iter($e);
+        iter
+    }};
+}
+
+pub mod constraints;
+pub mod deep;
+pub mod fri;
+pub mod merkle;
+pub mod proof;
+pub mod prover;
+pub mod trace;
+pub mod transcript;
+pub mod verifier;
+pub mod zk;
+
+pub use proof::{options::ProofOptions, StarkProof};
+pub use prover::{prove, ProveError};
+pub use verifier::{verify, VerifierError};
+
 #[cfg(test)]
 mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+    use lambdaworks_math::polynomial::Polynomial;
+
     use super::*;
+    use crate::{
+        constraints::{
+            check::{check_trace, AirError, ConstraintViolation},
+            composition_parts,
+            fibonacci::{FibonacciAir, FibonacciPublicInputs},
+            Air, BoundaryAssertion, Frame,
+        },
+        fri::decommit::FriVerifyError,
+        merkle::backends::HashFunction,
+        proof::{options::ProofOptionsError, ProofDecodeError, PROOF_VERSION},
+        prover::prove_unchecked,
+        trace::{fibonacci_trace, TraceTable},
+    };
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    fn options() -> ProofOptions {
+        ProofOptions {
+            blowup_factor: 8,
+            number_of_queries: 8,
+            ..ProofOptions::default()
+        }
+    }
+
+    fn fibonacci(trace_length: usize) -> (FibonacciAir<F>, TraceTable<F>) {
+        let trace = fibonacci_trace::<F>(trace_length);
+        let air = FibonacciAir {
+            trace_length,
+            public_inputs: FibonacciPublicInputs::from_trace(&trace),
+        };
+        (air, trace)
+    }
+
+    // t(gx) = t(x) + c(x) with t(1) = 0 and the last row public, c cycling through 1, 2, 3, 4
+    struct PeriodicAir {
+        trace_length: usize,
+        result: FE,
+    }
+
+    impl Air<F> for PeriodicAir {
+        fn trace_length(&self) -> usize {
+            self.trace_length
+        }
+
+        fn trace_width(&self) -> usize {
+            1
+        }
+
+        fn transition_offsets(&self) -> Vec<usize> {
+            vec![0, 1]
+        }
+
+        fn transition_exemptions(&self) -> usize {
+            1
+        }
+
+        fn evaluate_transition(&self, frame: &Frame<F>, periodic_values: &[FE]) -> Vec<FE> {
+            vec![frame.get_row(1)[0] - frame.get_row(0)[0] - periodic_values[0]]
+        }
+
+        fn transition_degrees(&self) -> Vec<usize> {
+            vec![1]
+        }
+
+        fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+            vec![
+                BoundaryAssertion {
+                    column: 0,
+                    row: 0,
+                    value: FE::zero(),
+                },
+                BoundaryAssertion {
+                    column: 0,
+                    row: self.trace_length - 1,
+                    value: self.result,
+                },
+            ]
+        }
+
+        fn periodic_columns(&self) -> Vec<Vec<FE>> {
+            vec![(1..=4).map(FE::from).collect()]
+        }
+    }
+
+    fn periodic_trace(trace_length: usize, constants: &[u64]) -> TraceTable<F> {
+        let mut column = vec![FE::zero()];
+        for i in 0..trace_length - 1 {
+            column.push(column[i] + FE::from(constants[i % constants.len()]));
+        }
+        TraceTable::new(column, 1)
+    }
+
+    // t(gx) = 2 t(x) on even rows only, but for the exempted row 6
+    struct SteppedAir;
+
+    impl Air<F> for SteppedAir {
+        fn trace_length(&self) -> usize {
+            16
+        }
+
+        fn trace_width(&self) -> usize {
+            1
+        }
+
+        fn transition_offsets(&self) -> Vec<usize> {
+            vec![0, 1]
+        }
+
+        fn transition_step(&self) -> usize {
+            2
+        }
+
+        fn transition_exempted_rows(&self) -> Vec<usize> {
+            vec![6]
+        }
+
+        fn evaluate_transition(&self, frame: &Frame<F>, _periodic_values: &[FE]) -> Vec<FE> {
+            vec![frame.get_row(1)[0] - frame.get_row(0)[0].double()]
+        }
+
+        fn transition_degrees(&self) -> Vec<usize> {
+            vec![1]
+        }
+
+        fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+            vec![BoundaryAssertion {
+                column: 0,
+                row: 0,
+                value: FE::one(),
+            }]
+        }
+    }
+
+    fn stepped_trace(broken_row: usize) -> TraceTable<F> {
+        let mut column = vec![FE::one()];
+        for i in 0..15 {
+            let next = if i % 2 == 0 && i != broken_row {
+                column[i].double()
+            } else {
+                FE::from(100 + i as u64)
+            };
+            column.push(next);
+        }
+        TraceTable::new(column, 1)
+    }
+
+    // t(gx) = t(x)^3 from t(1) = 2, declaring `transition_degree` for its constraint
+    struct CubeAir {
+        transition_degree: usize,
+    }
+
+    impl Air<F> for CubeAir {
+        fn trace_length(&self) -> usize {
+            16
+        }
+
+        fn trace_width(&self) -> usize {
+            1
+        }
+
+        fn transition_offsets(&self) -> Vec<usize> {
+            vec![0, 1]
+        }
+
+        fn transition_exemptions(&self) -> usize {
+            1
+        }
+
+        fn evaluate_transition(&self, frame: &Frame<F>, _periodic_values: &[FE]) -> Vec<FE> {
+            vec![frame.get_row(1)[0] - frame.get_row(0)[0].pow(3u64)]
+        }
+
+        fn transition_degrees(&self) -> Vec<usize> {
+            vec![self.transition_degree]
+        }
+
+        fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+            vec![BoundaryAssertion {
+                column: 0,
+                row: 0,
+                value: FE::from(2),
+            }]
+        }
+    }
+
+    fn cube_trace() -> TraceTable<F> {
+        let mut column = vec![FE::from(2)];
+        for i in 0..15 {
+            column.push(column[i].pow(3u64));
+        }
+        TraceTable::new(column, 1)
+    }
+
+    // (a, b) -> (b, a + b) from (1, 1), the last row public
+    struct TwoColumnFibonacciAir {
+        trace_length: usize,
+        result: FE,
+    }
+
+    impl Air<F> for TwoColumnFibonacciAir {
+        fn trace_length(&self) -> usize {
+            self.trace_length
+        }
+
+        fn trace_width(&self) -> usize {
+            2
+        }
+
+        fn transition_offsets(&self) -> Vec<usize> {
+            vec![0, 1]
+        }
+
+        fn transition_exemptions(&self) -> usize {
+            1
+        }
+
+        fn evaluate_transition(&self, frame: &Frame<F>, _periodic_values: &[FE]) -> Vec<FE> {
+            let (current, next) = (frame.get_row(0), frame.get_row(1));
+            vec![next[0] - current[1], next[1] - current[0] - current[1]]
+        }
+
+        fn transition_degrees(&self) -> Vec<usize> {
+            vec![1, 1]
+        }
+
+        fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+            vec![
+                BoundaryAssertion {
+                    column: 0,
+                    row: 0,
+                    value: FE::one(),
+                },
+                BoundaryAssertion {
+                    column: 1,
+                    row: 0,
+                    value: FE::one(),
+                },
+                BoundaryAssertion {
+                    column: 1,
+                    row: self.trace_length - 1,
+                    value: self.result,
+                },
+            ]
+        }
+    }
+
+    fn two_column_fibonacci(trace_length: usize) -> (TwoColumnFibonacciAir, TraceTable<F>) {
+        let mut rows = vec![[FE::one(), FE::one()]];
+        for i in 0..trace_length - 1 {
+            let [a, b] = rows[i];
+            rows.push([b, a + b]);
+        }
+        let air = TwoColumnFibonacciAir {
+            trace_length,
+            result: rows[trace_length - 1][1],
+        };
+        (air, TraceTable::new(rows.concat(), 2))
+    }
+
+    #[test]
+    fn two_column_proof_verifies() {
+        let (air, trace) = two_column_fibonacci(32);
+        assert_eq!(check_trace(&air, &trace), Ok(()));
+        let proof = prove(&air, &trace, &options()).unwrap();
+
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn tampered_second_column_is_rejected() {
+        let (air, trace) = two_column_fibonacci(32);
+        let mut proof = prove(&air, &trace, &options()).unwrap();
+
+        let opened = proof.openings[0].trace_frame[0][1];
+        proof.openings[0].trace_frame[0][1] += FE::one();
+        assert_eq!(
+            verify(&air, &proof, &options()),
+            Err(VerifierError::TraceOpeningInvalid)
+        );
+        proof.openings[0].trace_frame[0][1] = opened;
+
+        proof.ood_trace[0][1] += FE::one();
+        assert!(verify(&air, &proof, &options()).is_err());
+    }
+
+    // a constant column read at the given offsets
+    struct ConstantAir {
+        offsets: Vec<usize>,
+        step: usize,
+        exemptions: usize,
+        exempted_rows: Option<Vec<usize>>,
+    }
+
+    impl Default for ConstantAir {
+        fn default() -> Self {
+            ConstantAir {
+                offsets: vec![0, 1],
+                step: 1,
+                exemptions: 1,
+                exempted_rows: None,
+            }
+        }
+    }
+
+    impl Air<F> for ConstantAir {
+        fn trace_length(&self) -> usize {
+            16
+        }
+
+        fn trace_width(&self) -> usize {
+            1
+        }
+
+        fn transition_offsets(&self) -> Vec<usize> {
+            self.offsets.clone()
+        }
+
+        fn transition_exemptions(&self) -> usize {
+            self.exemptions
+        }
+
+        fn transition_step(&self) -> usize {
+            self.step
+        }
+
+        // the default rows unless overridden
+        fn transition_exempted_rows(&self) -> Vec<usize> {
+            match &self.exempted_rows {
+                Some(rows) => rows.clone(),
+                None => (1..=self.exemptions).map(|k| 16 - k * self.step).collect(),
+            }
+        }
+
+        fn evaluate_transition(&self, frame: &Frame<F>, _periodic_values: &[FE]) -> Vec<FE> {
+            vec![frame.get_row(1)[0] - frame.get_row(0)[0]]
+        }
+
+        fn transition_degrees(&self) -> Vec<usize> {
+            vec![1]
+        }
+
+        fn boundary_assertions(&self) -> Vec<BoundaryAssertion<F>> {
+            vec![]
+        }
+    }
 
     #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+    fn fibonacci_proof_verifies() {
+        let (air, trace) = fibonacci(32);
+        let proof = prove(&air, &trace, &options()).unwrap();
+
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn proofs_verify_with_every_hash_function() {
+        let (air, trace) = fibonacci(16);
+        for hash in [
+            HashFunction::Keccak256,
+            HashFunction::Blake2s256,
+            HashFunction::Poseidon,
+        ] {
+            let options = ProofOptions { hash, ..options() };
+            let mut proof = prove(&air, &trace, &options).unwrap();
+            assert_eq!(verify(&air, &proof, &options), Ok(()));
+
+            let swapped = ProofOptions {
+                hash: match hash {
+                    HashFunction::Keccak256 => HashFunction::Blake2s256,
+                    _ => HashFunction::Keccak256,
+                },
+                ..options
+            };
+            // a proof claiming another hash function than the verifier expects
+            proof.options = swapped.clone();
+            assert_eq!(
+                verify(&air, &proof, &options),
+                Err(VerifierError::OptionsMismatch)
+            );
+            // the hash function is absorbed with the options, so reading the proof under
+            // another one replays another transcript and samples another ood point
+            assert_eq!(
+                verify(&air, &proof, &swapped),
+                Err(VerifierError::OodCompositionMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn proofs_verify_with_every_folding_factor() {
+        let (air, trace) = fibonacci(64);
+        for fri_folding_factor in [4, 8] {
+            for zero_knowledge in [false, true] {
+                let options = ProofOptions {
+                    fri_folding_factor,
+                    zero_knowledge,
+                    ..options()
+                };
+                let mut proof = prove(&air, &trace, &options).unwrap();
+                assert_eq!(verify(&air, &proof, &options), Ok(()));
+
+                proof.layers[0].cosets[0][1] += FE::one();
+                assert!(verify(&air, &proof, &options).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn proof_of_work_is_checked() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            grinding_bits: 8,
+            ..options()
+        };
+        let mut proof = prove(&air, &trace, &options).unwrap();
+        assert_eq!(verify(&air, &proof, &options), Ok(()));
+
+        // the prover sends the smallest valid nonce, so every smaller one is invalid
+        assert!(proof.nonce > 0);
+        proof.nonce -= 1;
+        assert_eq!(
+            verify(&air, &proof, &options),
+            Err(VerifierError::Fri(FriVerifyError::InvalidProofOfWork))
+        );
+    }
+
+    // non zero knowledge proofs are deterministic, and the parallel feature must not change
+    // a single byte of them; the digests hold for the lambdaworks version pinned in the
+    // workspace manifest
+    #[test]
+    fn proof_bytes_are_fixed() {
+        use sha3::{Digest, Keccak256};
+
+        let (air, trace) = fibonacci(32);
+        // the second one goes through the parallel grinding search and coset leaves
+        let cases = [
+            (
+                options(),
+                "7a70402ab6d4ab704249ee2dab2044c3850c7be86c0d4f14a3c4f01fcdbe4eb4",
+            ),
+            (
+                ProofOptions {
+                    grinding_bits: 8,
+                    fri_folding_factor: 4,
+                    hash: HashFunction::Poseidon,
+                    ..options()
+                },
+                "db6a2161148b2492a0b0c027eb5c5dd186086521194d2f22b269a879f1d78238",
+            ),
+        ];
+
+        for (options, digest) in cases {
+            let proof = prove(&air, &trace, &options).unwrap();
+            assert_eq!(hex::encode(Keccak256::digest(proof.to_bytes())), digest);
+        }
+    }
+
+    #[test]
+    fn zero_knowledge_proof_verifies() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            zero_knowledge: true,
+            ..options()
+        };
+        let proof = prove(&air, &trace, &options).unwrap();
+        assert_eq!(verify(&air, &proof, &options), Ok(()));
+
+        let bytes = proof.to_bytes();
+        let decoded = StarkProof::<F>::from_bytes(&bytes).unwrap();
+        assert_eq!(verify(&air, &decoded, &options), Ok(()));
+
+        // fresh randomness every time, down to the trace commitment
+        let other = prove(&air, &trace, &options).unwrap();
+        assert_ne!(proof.trace_root, other.trace_root);
+    }
+
+    #[test]
+    fn zero_knowledge_needs_more_randomness_than_openings() {
+        let (air, trace) = fibonacci(8);
+        let options = ProofOptions {
+            number_of_queries: 3,
+            zero_knowledge: true,
+            ..options()
+        };
+
+        assert_eq!(
+            prove(&air, &trace, &options).err(),
+            Some(ProveError::InvalidOptions(
+                ProofOptionsError::ZeroKnowledgeTooManyOpenings {
+                    openings: 12,
+                    trace_length: 8
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn zero_knowledge_salts_are_authenticated() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            zero_knowledge: true,
+            ..options()
+        };
+        let mut proof = prove(&air, &trace, &options).unwrap();
+        proof.openings[0].salts[0] += FE::one();
+
+        assert_eq!(
+            verify(&air, &proof, &options),
+            Err(VerifierError::TraceOpeningInvalid)
+        );
+    }
+
+    #[test]
+    fn proof_survives_serialization() {
+        let (air, trace) = fibonacci(16);
+        let bytes = prove(&air, &trace, &options()).unwrap().to_bytes();
+        let proof = StarkProof::<F>::from_bytes(&bytes).unwrap();
+
+        assert_eq!(proof.to_bytes(), bytes);
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn malformed_proof_bytes_are_rejected() {
+        let (air, trace) = fibonacci(16);
+        let bytes = prove(&air, &trace, &options()).unwrap().to_bytes();
+        let decode = |bytes: &[u8]| StarkProof::<F>::from_bytes(bytes).err();
+        let modified = |position: usize, value: u8| {
+            let mut bytes = bytes.clone();
+            bytes[position] = value;
+            bytes
+        };
+
+        for length in 0..bytes.len() {
+            assert_eq!(
+                decode(&bytes[..length]),
+                Some(ProofDecodeError::UnexpectedEnd)
+            );
+        }
+        assert_eq!(
+            decode(&[bytes.as_slice(), &[0; 3]].concat()),
+            Some(ProofDecodeError::TrailingBytes(3))
+        );
+
+        assert_eq!(
+            decode(&modified(0, b'X')),
+            Some(ProofDecodeError::InvalidMagic)
+        );
+        assert_eq!(
+            decode(&modified(4, PROOF_VERSION + 1)),
+            Some(ProofDecodeError::UnsupportedVersion(PROOF_VERSION + 1))
+        );
+        assert_eq!(
+            decode(&modified(5, 31)),
+            Some(ProofDecodeError::FieldElementSizeMismatch {
+                expected: 32,
+                found: 31
+            })
+        );
+        // the options follow as eight big endian u64, the hash tag and the zero knowledge
+        // flag last
+        let options_start = 6;
+        assert_eq!(
+            decode(&modified(options_start + 7 * 8 - 1, 7)),
+            Some(ProofDecodeError::UnknownHashFunction(7))
+        );
+        assert_eq!(
+            decode(&modified(options_start + 8 * 8 - 1, 2)),
+            Some(ProofDecodeError::InvalidZeroKnowledgeFlag(2))
+        );
+    }
+
+    #[test]
+    fn proof_json_mirrors_the_proof() {
+        let (air, trace) = fibonacci(16);
+        let proof = prove(&air, &trace, &options()).unwrap();
+        let json = proof.to_json();
+
+        assert_eq!(json["version"], PROOF_VERSION);
+        assert_eq!(json["options"]["blowup_factor"], 8);
+        assert_eq!(json["options"]["hash"], "keccak");
+        assert_eq!(json["trace_root"], hex::encode(proof.trace_root));
+        assert_eq!(json["openings"].as_array().unwrap().len(), 8);
+        assert_eq!(json["layers"].as_array().unwrap().len(), proof.layers.len());
+        assert_eq!(
+            json["last_layer_poly"][0],
+            hex::encode(proof.last_layer_poly.coefficients()[0].to_bytes_be())
+        );
+        assert_eq!(json["nonce"], proof.nonce);
+    }
+
+    #[test]
+    fn invalid_trace_is_rejected() {
+        let (air, trace) = fibonacci(32);
+        let mut column = trace.column(0);
+        column[17] += FE::one();
+        let proof =
+            prove_unchecked(&air, &TraceTable::from_columns(&[column]), &options()).unwrap();
+
+        assert!(verify(&air, &proof, &options()).is_err());
+    }
+
+    #[test]
+    fn wrong_public_result_is_rejected() {
+        let (air, trace) = fibonacci(32);
+        let proof = prove(&air, &trace, &options()).unwrap();
+
+        let mut public_inputs = air.public_inputs.clone();
+        public_inputs.result += FE::one();
+        let claimed = FibonacciAir {
+            trace_length: 32,
+            public_inputs,
+        };
+        assert!(verify(&claimed, &proof, &options()).is_err());
+    }
+
+    #[test]
+    fn tampered_last_layer_is_rejected() {
+        let (air, trace) = fibonacci(32);
+        let mut proof = prove(&air, &trace, &options()).unwrap();
+        let mut coefficients = proof.last_layer_poly.coefficients().to_vec();
+        coefficients[0] += FE::one();
+        proof.last_layer_poly = Polynomial::new(&coefficients);
+
+        // the coefficients are absorbed before the queries are drawn, so the openings no
+        // longer match the queries; the last layer check itself is tested in fri::decommit
+        assert_eq!(
+            verify(&air, &proof, &options()),
+            Err(VerifierError::Fri(FriVerifyError::MerklePathInvalid {
+                layer: 0
+            }))
+        );
+    }
+
+    #[test]
+    fn tampered_fri_layers_are_rejected_by_the_matching_check() {
+        let (air, trace) = fibonacci(32);
+        let proof = || prove(&air, &trace, &options()).unwrap();
+        let fri_error = |proof| match verify(&air, &proof, &options()) {
+            Err(VerifierError::Fri(error)) => error,
+            result => panic!("expected a fri error, got {result:?}"),
+        };
+
+        // the first layer is only bound by its tree
+        let mut tampered = proof();
+        tampered.layers[0].cosets[0][0] += FE::one();
+        assert_eq!(
+            fri_error(tampered),
+            FriVerifyError::MerklePathInvalid { layer: 0 }
+        );
+
+        // a later layer is checked against the fold of the previous one before its tree
+        let mut tampered = proof();
+        for value in tampered.layers[1].cosets[0].iter_mut() {
+            *value += FE::one();
+        }
+        assert_eq!(
+            fri_error(tampered),
+            FriVerifyError::FoldingMismatch { layer: 1, query: 0 }
+        );
+
+        let mut tampered = proof();
+        tampered.layers[1].domain_size *= 2;
+        assert_eq!(
+            fri_error(tampered),
+            FriVerifyError::DomainSizeMismatch { layer: 1 }
+        );
+
+        let mut tampered = proof();
+        tampered.layers[0].cosets.pop();
+        assert_eq!(
+            fri_error(tampered),
+            FriVerifyError::QueryCountMismatch { layer: 0 }
+        );
+    }
+
+    #[test]
+    fn last_layer_is_bounded_by_the_degree_the_folds_leave() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            fri_folding_factor: 8,
+            ..options()
+        };
+        assert_eq!(options.fri_last_layer_size(32), 1);
+
+        let mut proof = prove(&air, &trace, &options).unwrap();
+        assert_eq!(proof.last_layer_poly.degree(), 0);
+        let mut coefficients = proof.last_layer_poly.coefficients().to_vec();
+        coefficients.push(FE::one());
+        proof.last_layer_poly = Polynomial::new(&coefficients);
+
+        assert_eq!(
+            verify(&air, &proof, &options),
+            Err(VerifierError::Fri(FriVerifyError::LastLayerDegreeTooHigh {
+                degree: 1,
+                bound: 0
+            }))
+        );
+    }
+
+    #[test]
+    fn proof_for_another_trace_length_is_rejected() {
+        let (air, trace) = fibonacci(32);
+        let proof = prove(&air, &trace, &options()).unwrap();
+
+        assert!(verify(&fibonacci(64).0, &proof, &options()).is_err());
+    }
+
+    #[test]
+    fn periodic_column_proof_verifies() {
+        let trace = periodic_trace(64, &[1, 2, 3, 4]);
+        let air = PeriodicAir {
+            trace_length: 64,
+            result: trace.row(63)[0],
+        };
+        let proof = prove(&air, &trace, &options()).unwrap();
+
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn trace_ignoring_periodic_column_is_rejected() {
+        let trace = periodic_trace(64, &[1, 2, 3, 5]);
+        let air = PeriodicAir {
+            trace_length: 64,
+            result: trace.row(63)[0],
+        };
+        let proof = prove_unchecked(&air, &trace, &options()).unwrap();
+
+        assert!(verify(&air, &proof, &options()).is_err());
+    }
+
+    #[test]
+    fn stepped_transition_with_exempted_row_verifies() {
+        let proof = prove(&SteppedAir, &stepped_trace(6), &options()).unwrap();
+
+        assert_eq!(verify(&SteppedAir, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn stepped_transition_is_enforced_on_every_constrained_row() {
+        let proof = prove_unchecked(&SteppedAir, &stepped_trace(4), &options()).unwrap();
+
+        assert!(verify(&SteppedAir, &proof, &options()).is_err());
+    }
+
+    #[test]
+    fn trace_check_reports_the_offending_row() {
+        let (air, trace) = fibonacci(32);
+        assert_eq!(check_trace(&air, &trace), Ok(()));
+
+        let mut column = trace.column(0);
+        column[17] += FE::one();
+        assert_eq!(
+            check_trace(&air, &TraceTable::from_columns(&[column])),
+            Err(ConstraintViolation::Transition {
+                constraint: 0,
+                row: 15
+            })
+        );
+
+        let (other_air, _) = fibonacci(64);
+        let claimed = FibonacciAir {
+            trace_length: 32,
+            public_inputs: other_air.public_inputs,
+        };
+        assert_eq!(
+            check_trace(&claimed, &trace),
+            Err(ConstraintViolation::Boundary {
+                assertion: 2,
+                row: 31
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_prover_stops_at_the_offending_row() {
+        assert_eq!(
+            prove(&SteppedAir, &stepped_trace(4), &options()).err(),
+            Some(ProveError::ConstraintViolation(
+                ConstraintViolation::Transition {
+                    constraint: 0,
+                    row: 4
+                }
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "above the")]
+    fn debug_prover_catches_understated_constraint_degrees() {
+        let air = CubeAir {
+            transition_degree: 1,
+        };
+        prove(&air, &cube_trace(), &options()).unwrap();
+    }
+
+    #[test]
+    fn high_degree_composition_is_split_into_parts() {
+        let air = CubeAir {
+            transition_degree: 3,
+        };
+        let proof = prove(&air, &cube_trace(), &options()).unwrap();
+        assert_eq!(proof.ood_composition.len(), 2);
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+
+        let options = ProofOptions {
+            zero_knowledge: true,
+            // 7 queries and the ood frame open 16 values of each column, all the randomness
+            number_of_queries: 7,
+            ..options()
+        };
+        let proof = prove(&air, &cube_trace(), &options).unwrap();
+        assert_eq!(proof.ood_composition.len(), 4);
+        assert_eq!(verify(&air, &proof, &options), Ok(()));
+    }
+
+    #[test]
+    fn split_composition_parts_are_bound_to_the_constraints() {
+        let air = CubeAir {
+            transition_degree: 3,
+        };
+        let mut proof = prove(&air, &cube_trace(), &options()).unwrap();
+        proof.ood_composition[1] += FE::one();
+
+        assert_eq!(
+            verify(&air, &proof, &options()),
+            Err(VerifierError::OodCompositionMismatch)
+        );
+    }
+
+    #[test]
+    fn composition_parts_must_fit_the_blowup() {
+        let air = CubeAir {
+            transition_degree: 3,
+        };
+        let options = ProofOptions {
+            blowup_factor: 4,
+            zero_knowledge: true,
+            ..options()
+        };
+
+        assert_eq!(composition_parts(&air, options.trace_degree_bound(16)), 3);
+        assert_eq!(
+            prove(&air, &cube_trace(), &options).err(),
+            Some(ProveError::InvalidOptions(
+                ProofOptionsError::TooManyCompositionParts {
+                    parts: 3,
+                    blowup_factor: 4
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn prover_rejects_a_trace_of_another_shape() {
+        let (air, _) = fibonacci(32);
+
+        assert_eq!(
+            prove(&air, &fibonacci_trace::<F>(16), &options()).err(),
+            Some(ProveError::TraceShapeMismatch {
+                expected: (32, 1),
+                found: (16, 1)
+            })
+        );
+        assert_eq!(
+            prove(&air, &two_column_fibonacci(32).1, &options()).err(),
+            Some(ProveError::TraceShapeMismatch {
+                expected: (32, 1),
+                found: (32, 2)
+            })
+        );
+    }
+
+    #[test]
+    fn transition_offsets_must_start_at_the_current_row() {
+        let trace = TraceTable::new(vec![FE::one(); 16], 1);
+        let proof = prove(&fibonacci(16).0, &fibonacci_trace::<F>(16), &options()).unwrap();
+
+        for offsets in [vec![1, 2], vec![]] {
+            let air = ConstantAir {
+                offsets,
+                ..ConstantAir::default()
+            };
+            assert_eq!(
+                prove(&air, &trace, &options()).err(),
+                Some(ProveError::InvalidAir(AirError::InvalidTransitionOffsets))
+            );
+            assert_eq!(
+                verify(&air, &proof, &options()),
+                Err(VerifierError::InvalidAir(
+                    AirError::InvalidTransitionOffsets
+                ))
+            );
+        }
+
+        let air = ConstantAir::default();
+        let proof = prove(&air, &trace, &options()).unwrap();
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn exempted_rows_must_be_constrained_rows() {
+        let trace = TraceTable::new(vec![FE::one(); 16], 1);
+        let proof = prove(&fibonacci(16).0, &fibonacci_trace::<F>(16), &options()).unwrap();
+        let cases = [
+            (
+                ConstantAir {
+                    step: 3,
+                    ..ConstantAir::default()
+                },
+                AirError::InvalidTransitionStep(3),
+            ),
+            (
+                ConstantAir {
+                    exemptions: 17,
+                    ..ConstantAir::default()
+                },
+                AirError::TooManyTransitionExemptions(17),
+            ),
+            (
+                ConstantAir {
+                    step: 2,
+                    exempted_rows: Some(vec![3]),
+                    ..ConstantAir::default()
+                },
+                AirError::InvalidExemptedRows,
+            ),
+            (
+                ConstantAir {
+                    exempted_rows: Some(vec![16]),
+                    ..ConstantAir::default()
+                },
+                AirError::InvalidExemptedRows,
+            ),
+            (
+                ConstantAir {
+                    exempted_rows: Some(vec![4, 4]),
+                    ..ConstantAir::default()
+                },
+                AirError::InvalidExemptedRows,
+            ),
+        ];
+
+        for (air, error) in cases {
+            assert_eq!(
+                prove(&air, &trace, &options()).err(),
+                Some(ProveError::InvalidAir(error))
+            );
+            assert!(matches!(
+                verify(&air, &proof, &options()),
+                Err(VerifierError::InvalidAir(_))
+            ));
+        }
+
+        // overridden exempted rows need no exemption count
+        let air = ConstantAir {
+            exemptions: 0,
+            exempted_rows: Some(vec![4, 9]),
+            ..ConstantAir::default()
+        };
+        let proof = prove(&air, &trace, &options()).unwrap();
+        assert_eq!(verify(&air, &proof, &options()), Ok(()));
+    }
+
+    #[test]
+    fn prover_rejects_invalid_options() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            fri_folding_factor: 3,
+            ..options()
+        };
+
+        assert_eq!(
+            prove(&air, &trace, &options).err(),
+            Some(ProveError::InvalidOptions(
+                ProofOptionsError::UnsupportedFoldingFactor(3)
+            ))
+        );
+    }
+
+    #[test]
+    fn proof_with_weaker_options_is_rejected() {
+        let (air, trace) = fibonacci(32);
+        let weak = ProofOptions {
+            blowup_factor: 2,
+            number_of_queries: 1,
+            ..ProofOptions::default()
+        };
+        let proof = prove(&air, &trace, &weak).unwrap();
+
+        assert_eq!(verify(&air, &proof, &weak), Ok(()));
+        assert_eq!(
+            verify(&air, &proof, &options()),
+            Err(VerifierError::OptionsMismatch)
+        );
+    }
+
+    #[test]
+    fn queries_are_bounded_by_the_lde_domain() {
+        let (air, trace) = fibonacci(32);
+        let options = ProofOptions {
+            number_of_queries: usize::MAX,
+            ..options()
+        };
+
+        assert_eq!(
+            prove(&air, &trace, &options).err(),
+            Some(ProveError::InvalidOptions(
+                ProofOptionsError::TooManyQueries {
+                    queries: usize::MAX,
+                    domain_size: 256
+                }
+            ))
+        );
     }
 }
@@ -0,0 +1,127 @@
+use std::{fmt, marker::PhantomData};
+
+use blake2::Blake2s256;
+use lambdaworks_crypto::{
+    hash::poseidon::{starknet::PoseidonCairoStark252, Poseidon},
+    merkle_tree::{
+        backends::{field_element_vector::FieldElementVectorBackend, types::BatchKeccak256Backend},
+        traits::IsMerkleTreeBackend,
+    },
+};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        traits::IsField,
+    },
+    traits::{AsBytes, ByteConversion},
+};
+
+// every commitment of a proof hashes rows of field elements into 32 byte nodes
+pub trait CommitmentBackend<F: IsField>:
+    IsMerkleTreeBackend<Node = [u8; 32], Data = Vec<FieldElement<F>>>
+{
+}
+
+impl<F, B> CommitmentBackend<F> for B
+where
+    F: IsField,
+    B: IsMerkleTreeBackend<Node = [u8; 32], Data = Vec<FieldElement<F>>>,
+{
+}
+
+pub type KeccakBackend<F> = BatchKeccak256Backend<F>;
+pub type Blake2sBackend<F> = FieldElementVectorBackend<F, Blake2s256, 32>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashFunction {
+    Keccak256,
+    Blake2s256,
+    Poseidon,
+}
+
+impl HashFunction {
+    pub fn tag(&self) -> u64 {
+        match self {
+            HashFunction::Keccak256 => 0,
+            HashFunction::Blake2s256 => 1,
+            HashFunction::Poseidon => 2,
+        }
+    }
+
+    pub fn from_tag(tag: u64) -> Option<Self> {
+        match tag {
+            0 => Some(HashFunction::Keccak256),
+            1 => Some(HashFunction::Blake2s256),
+            2 => Some(HashFunction::Poseidon),
+            _ => None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "keccak" => Some(HashFunction::Keccak256),
+            "blake2s" => Some(HashFunction::Blake2s256),
+            "poseidon" => Some(HashFunction::Poseidon),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashFunction::Keccak256 => write!(f, "keccak"),
+            HashFunction::Blake2s256 => write!(f, "blake2s"),
+            HashFunction::Poseidon => write!(f, "poseidon"),
+        }
+    }
+}
+
+type Felt = FieldElement<Stark252PrimeField>;
+
+// starknet poseidon over Stark252PrimeField, cheap to verify inside another stark over the
+// same field; elements of smaller fields are embedded through their big endian encoding
+// and nodes are the big endian encoding of the hash
+#[derive(Clone)]
+pub struct PoseidonBackend<F> {
+    phantom: PhantomData<F>,
+}
+
+impl<F> Default for PoseidonBackend<F> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+fn embed<F: IsField>(element: &FieldElement<F>) -> Felt
+where
+    FieldElement<F>: AsBytes,
+{
+    let bytes = element.as_bytes();
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    Felt::from_bytes_be(&padded).unwrap()
+}
+
+impl<F> IsMerkleTreeBackend for PoseidonBackend<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    type Node = [u8; 32];
+    type Data = Vec<FieldElement<F>>;
+
+    fn hash_data(input: &Vec<FieldElement<F>>) -> [u8; 32] {
+        let inputs: Vec<Felt> = input.iter().map(embed).collect();
+        PoseidonCairoStark252::hash_many(&inputs).to_bytes_be()
+    }
+
+    fn hash_new_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        // nodes coming from a proof may exceed the modulus, they are reduced like any other
+        let left = Felt::from_bytes_be(left).unwrap();
+        let right = Felt::from_bytes_be(right).unwrap();
+        PoseidonCairoStark252::hash(&left, &right).to_bytes_be()
+    }
+}
@@ -0,0 +1,194 @@
+pub mod backends;
+
+use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
+
+// siblings needed to authenticate a set of leaves together, listed level by level from the
+// leaves up and left to right, skipping every node computable from the opened leaves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiProof<T> {
+    pub nodes: Vec<T>,
+}
+
+impl<T: Clone + PartialEq + Eq> MultiProof<T> {
+    pub fn build<B>(tree: &MerkleTree<B>, positions: &[usize]) -> Self
+    where
+        B: IsMerkleTreeBackend<Node = T>,
+    {
+        let mut positions = positions.to_vec();
+        positions.sort_unstable();
+        positions.dedup();
+
+        let paths: Vec<Vec<T>> = positions
+            .iter()
+            .map(|position| tree.get_proof_by_pos(*position).unwrap().merkle_path)
+            .collect();
+        // siblings[level][i] is the sibling at `level` on the path of the i-th position
+        let depth = paths.first().map_or(0, |path| path.len());
+        let siblings: Vec<Vec<T>> = (0..depth)
+            .map(|level| paths.iter().map(|path| path[level].clone()).collect())
+            .collect();
+
+        // (node index at the current level, path of a leaf below it)
+        let mut known: Vec<(usize, usize)> = positions.iter().copied().zip(0..).collect();
+        let mut nodes = vec![];
+        for level_siblings in &siblings {
+            let mut parents: Vec<(usize, usize)> = vec![];
+            let mut i = 0;
+            while i < known.len() {
+                let (index, path) = known[i];
+                if known.get(i + 1).is_some_and(|(next, _)| *next == index ^ 1) {
+                    i += 2;
+                } else {
+                    nodes.push(level_siblings[path].clone());
+                    i += 1;
+                }
+                parents.push((index / 2, path));
+            }
+            known = parents;
+        }
+
+        Self { nodes }
+    }
+
+    pub fn verify<B>(&self, root: &T, depth: usize, leaves: &[(usize, B::Data)]) -> bool
+    where
+        B: IsMerkleTreeBackend<Node = T>,
+    {
+        let mut known: Vec<(usize, T)> = leaves
+            .iter()
+            .map(|(position, leaf)| (*position, B::hash_data(leaf)))
+            .collect();
+        known.sort_by_key(|(position, _)| *position);
+        // a leaf may be opened more than once, but always with the same value
+        for pair in known.windows(2) {
+            if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+                return false;
+            }
+        }
+        known.dedup_by_key(|(position, _)| *position);
+        if known.is_empty() || known.iter().any(|(position, _)| *position >> depth != 0) {
+            return false;
+        }
+
+        let mut siblings = self.nodes.iter();
+        for _ in 0..depth {
+            let mut parents = vec![];
+            let mut i = 0;
+            while i < known.len() {
+                let (index, node) = &known[i];
+                let sibling = match known.get(i + 1) {
+                    Some((next, next_node)) if *next == index ^ 1 => {
+                        i += 2;
+                        next_node
+                    }
+                    _ => {
+                        i += 1;
+                        match siblings.next() {
+                            Some(sibling) => sibling,
+                            None => return false,
+                        }
+                    }
+                };
+                let parent = if index % 2 == 0 {
+                    B::hash_new_parent(node, sibling)
+                } else {
+                    B::hash_new_parent(sibling, node)
+                };
+                parents.push((index / 2, parent));
+            }
+            known = parents;
+        }
+
+        siblings.next().is_none() && known.len() == 1 && &known[0].1 == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_crypto::merkle_tree::backends::types::Keccak256Backend;
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    const DEPTH: usize = 10;
+
+    fn tree() -> (MerkleTree<Keccak256Backend<F>>, Vec<FE>) {
+        let leaves: Vec<FE> = (0..1u64 << DEPTH).map(FE::from).collect();
+        (MerkleTree::build(&leaves), leaves)
+    }
+
+    fn opened(leaves: &[FE], positions: &[usize]) -> Vec<(usize, FE)> {
+        positions.iter().map(|p| (*p, leaves[*p])).collect()
+    }
+
+    #[test]
+    fn multiproof_of_a_single_leaf_is_its_path() {
+        let (tree, leaves) = tree();
+        let proof = MultiProof::build(&tree, &[37]);
+
+        assert_eq!(proof.nodes, tree.get_proof_by_pos(37).unwrap().merkle_path);
+        assert!(proof.verify::<Keccak256Backend<F>>(&tree.root, DEPTH, &opened(&leaves, &[37])));
+    }
+
+    #[test]
+    fn multiproof_verifies_unsorted_and_repeated_positions() {
+        let (tree, leaves) = tree();
+        let positions = [900, 3, 512, 3, 4, 1023, 0];
+        let proof = MultiProof::build(&tree, &positions);
+
+        assert!(proof.verify::<Keccak256Backend<F>>(
+            &tree.root,
+            DEPTH,
+            &opened(&leaves, &positions)
+        ));
+    }
+
+    #[test]
+    fn multiproof_rejects_a_wrong_leaf() {
+        let (tree, leaves) = tree();
+        let positions = [10, 11, 600];
+        let proof = MultiProof::build(&tree, &positions);
+        let mut tampered = opened(&leaves, &positions);
+        tampered[2].1 = FE::from(5);
+
+        assert!(!proof.verify::<Keccak256Backend<F>>(&tree.root, DEPTH, &tampered));
+    }
+
+    #[test]
+    fn multiproof_rejects_a_missing_or_extra_node() {
+        let (tree, leaves) = tree();
+        let positions = [1, 200, 201, 777];
+        let mut proof = MultiProof::build(&tree, &positions);
+        let leaves = opened(&leaves, &positions);
+
+        proof.nodes.push(tree.root);
+        assert!(!proof.verify::<Keccak256Backend<F>>(&tree.root, DEPTH, &leaves));
+        proof.nodes.truncate(proof.nodes.len() - 2);
+        assert!(!proof.verify::<Keccak256Backend<F>>(&tree.root, DEPTH, &leaves));
+    }
+
+    #[test]
+    fn multiproof_is_smaller_than_independent_paths() {
+        let (tree, _) = tree();
+        for (positions, max_nodes) in [
+            // siblings share every level
+            (vec![0, 1], 9),
+            // a whole subtree of 16 leaves only needs the path of its root
+            ((0..16).collect::<Vec<usize>>(), DEPTH - 4),
+            // scattered queries still share the top levels
+            (
+                vec![3, 130, 257, 390, 515, 640, 777, 901, 1000, 1022],
+                10 * DEPTH - 20,
+            ),
+        ] {
+            let independent = positions.len() * DEPTH;
+            let multiproof = MultiProof::build(&tree, &positions).nodes.len();
+            assert!(multiproof <= max_nodes && multiproof < independent);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+pub mod options;
+
+use std::fmt;
+
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+    traits::ByteConversion,
+};
+use serde_json::{json, Value};
+
+use crate::{fri::commit::LayerCommitment, merkle::MultiProof};
+
+use options::ProofOptions;
+
+pub const PROOF_MAGIC: &[u8; 4] = b"S101";
+pub const PROOF_VERSION: u8 = 12;
+
+// values of the trace lde rows at x, gx, g^2x, ... and of the composition columns at x for
+// one fri query, authenticated by the multiproofs of the stark proof; in zero knowledge mode
+// the salts of those leaves follow, trace rows first
+pub struct QueryOpening<F: IsField> {
+    pub trace_frame: Vec<Vec<FieldElement<F>>>,
+    pub composition_values: Vec<FieldElement<F>>,
+    pub salts: Vec<FieldElement<F>>,
+}
+
+pub struct StarkProof<F: IsField> {
+    pub options: ProofOptions,
+    pub trace_root: [u8; 32],
+    pub composition_root: [u8; 32],
+    pub trace_proof: MultiProof<[u8; 32]>,
+    pub composition_proof: MultiProof<[u8; 32]>,
+    pub ood_trace: Vec<Vec<FieldElement<F>>>,
+    pub ood_composition: Vec<FieldElement<F>>,
+    pub openings: Vec<QueryOpening<F>>,
+    pub layers: Vec<LayerCommitment<F>>,
+    pub last_layer_poly: Polynomial<FieldElement<F>>,
+    pub nonce: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofDecodeError {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    FieldElementSizeMismatch { expected: usize, found: usize },
+    UnexpectedEnd,
+    InvalidFieldElement,
+    UnknownHashFunction(u64),
+    InvalidZeroKnowledgeFlag(u64),
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for ProofDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofDecodeError::InvalidMagic => write!(f, "not a stark101 proof"),
+            ProofDecodeError::UnsupportedVersion(v) => write!(f, "unsupported proof version {v}"),
+            ProofDecodeError::FieldElementSizeMismatch { expected, found } => write!(
+                f,
+                "field element size mismatch: expected {expected} bytes, found {found}"
+            ),
+            ProofDecodeError::UnexpectedEnd => write!(f, "proof ended unexpectedly"),
+            ProofDecodeError::InvalidFieldElement => write!(f, "invalid field element encoding"),
+            ProofDecodeError::UnknownHashFunction(tag) => {
+                write!(f, "unknown commitment hash function {tag}")
+            }
+            ProofDecodeError::InvalidZeroKnowledgeFlag(flag) => {
+                write!(f, "invalid zero knowledge flag {flag}")
+            }
+            ProofDecodeError::TrailingBytes(n) => write!(f, "{n} trailing bytes after proof"),
+        }
+    }
+}
+
+impl std::error::Error for ProofDecodeError {}
+
+fn element_size<F: IsField>() -> usize
+where
+    FieldElement<F>: ByteConversion,
+{
+    FieldElement::<F>::zero().to_bytes_be().len()
+}
+
+// layout: magic | version | element size | options | trace root | composition root |
+// trace multiproof | composition multiproof | ood frame | ood composition | query openings |
+// layers | last layer coefficients | proof of work nonce,
+// counts are u32 and domain sizes u64, all big endian
+impl<F> StarkProof<F>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = PROOF_MAGIC.to_vec();
+        bytes.push(PROOF_VERSION);
+        bytes.push(element_size::<F>() as u8);
+        bytes.extend(self.options.to_bytes());
+        bytes.extend(self.trace_root);
+        bytes.extend(self.composition_root);
+        write_multiproof(&mut bytes, &self.trace_proof);
+        write_multiproof(&mut bytes, &self.composition_proof);
+
+        bytes.extend((self.ood_trace.len() as u32).to_be_bytes());
+        for row in &self.ood_trace {
+            write_elements(&mut bytes, row);
+        }
+        write_elements(&mut bytes, &self.ood_composition);
+
+        bytes.extend((self.openings.len() as u32).to_be_bytes());
+        for opening in &self.openings {
+            bytes.extend((opening.trace_frame.len() as u32).to_be_bytes());
+            for row in &opening.trace_frame {
+                write_elements(&mut bytes, row);
+            }
+            write_elements(&mut bytes, &opening.composition_values);
+            write_elements(&mut bytes, &opening.salts);
+        }
+
+        bytes.extend((self.layers.len() as u32).to_be_bytes());
+        for layer in &self.layers {
+            bytes.extend(layer.merkle_root);
+            bytes.extend((layer.domain_size as u64).to_be_bytes());
+            bytes.extend((layer.cosets.len() as u32).to_be_bytes());
+            for coset in &layer.cosets {
+                write_elements(&mut bytes, coset);
+            }
+            write_elements(&mut bytes, &layer.salts);
+            write_multiproof(&mut bytes, &layer.inclusion_proof);
+        }
+
+        write_elements(&mut bytes, self.last_layer_poly.coefficients());
+        bytes.extend(self.nonce.to_be_bytes());
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        if reader.take(PROOF_MAGIC.len())? != PROOF_MAGIC {
+            return Err(ProofDecodeError::InvalidMagic);
+        }
+        let version = reader.u8()?;
+        if version != PROOF_VERSION {
+            return Err(ProofDecodeError::UnsupportedVersion(version));
+        }
+        let size = reader.u8()? as usize;
+        if size != element_size::<F>() {
+            return Err(ProofDecodeError::FieldElementSizeMismatch {
+                expected: element_size::<F>(),
+                found: size,
+            });
+        }
+
+        let options = ProofOptions::from_bytes(reader.take(ProofOptions::ENCODED_LEN)?)?;
+        let trace_root = reader.node()?;
+        let composition_root = reader.node()?;
+        let trace_proof = reader.multiproof()?;
+        let composition_proof = reader.multiproof()?;
+
+        let ood_rows = reader.u32()?;
+        let ood_trace = (0..ood_rows)
+            .map(|_| reader.field_elements())
+            .collect::<Result<Vec<_>, _>>()?;
+        let ood_composition = reader.field_elements()?;
+
+        let number_of_openings = reader.u32()?;
+        let mut openings = vec![];
+        for _ in 0..number_of_openings {
+            let frame_rows = reader.u32()?;
+            let trace_frame = (0..frame_rows)
+                .map(|_| reader.field_elements())
+                .collect::<Result<Vec<_>, _>>()?;
+            openings.push(QueryOpening {
+                trace_frame,
+                composition_values: reader.field_elements()?,
+                salts: reader.field_elements()?,
+            });
+        }
+
+        let number_of_layers = reader.u32()?;
+        let mut layers = vec![];
+        for _ in 0..number_of_layers {
+            let merkle_root = reader.node()?;
+            let domain_size = reader.u64()? as usize;
+            let number_of_queries = reader.u32()?;
+            let cosets = (0..number_of_queries)
+                .map(|_| reader.field_elements())
+                .collect::<Result<Vec<_>, _>>()?;
+            let salts = reader.field_elements()?;
+            layers.push(LayerCommitment {
+                merkle_root,
+                domain_size,
+                inclusion_proof: reader.multiproof()?,
+                cosets,
+                salts,
+            });
+        }
+
+        let coefficients = reader.field_elements()?;
+        let nonce = reader.u64()?;
+
+        if reader.position != bytes.len() {
+            return Err(ProofDecodeError::TrailingBytes(
+                bytes.len() - reader.position,
+            ));
+        }
+
+        Ok(StarkProof {
+            options,
+            trace_root,
+            composition_root,
+            trace_proof,
+            composition_proof,
+            ood_trace,
+            ood_composition,
+            openings,
+            layers,
+            last_layer_poly: Polynomial::new(&coefficients),
+            nonce,
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        let element = |e: &FieldElement<F>| hex::encode(e.to_bytes_be());
+        let path =
+            |p: &MultiProof<[u8; 32]>| p.nodes.iter().map(hex::encode).collect::<Vec<String>>();
+
+        json!({
+            "version": PROOF_VERSION,
+            "options": {
+                "blowup_factor": self.options.blowup_factor,
+                "number_of_queries": self.options.number_of_queries,
+                "fri_folding_factor": self.options.fri_folding_factor,
+                "fri_last_layer_degree_bound": self.options.fri_last_layer_degree_bound,
+                "coset_offset": self.options.coset_offset,
+                "grinding_bits": self.options.grinding_bits,
+                "hash": self.options.hash.to_string(),
+                "zero_knowledge": self.options.zero_knowledge,
+            },
+            "trace_root": hex::encode(self.trace_root),
+            "composition_root": hex::encode(self.composition_root),
+            "trace_proof": path(&self.trace_proof),
+            "composition_proof": path(&self.composition_proof),
+            "ood_trace": self
+                .ood_trace
+                .iter()
+                .map(|row| row.iter().map(element).collect::<Vec<String>>())
+                .collect::<Vec<Vec<String>>>(),
+            "ood_composition": self.ood_composition.iter().map(element).collect::<Vec<String>>(),
+            "openings": self.openings.iter().map(|opening| json!({
+                "trace_frame": opening
+                    .trace_frame
+                    .iter()
+                    .map(|row| row.iter().map(element).collect::<Vec<String>>())
+                    .collect::<Vec<Vec<String>>>(),
+                "composition_values": opening
+                    .composition_values
+                    .iter()
+                    .map(element)
+                    .collect::<Vec<String>>(),
+                "salts": opening.salts.iter().map(element).collect::<Vec<String>>(),
+            })).collect::<Vec<Value>>(),
+            "layers": self.layers.iter().map(|layer| json!({
+                "merkle_root": hex::encode(layer.merkle_root),
+                "domain_size": layer.domain_size,
+                "cosets": layer
+                    .cosets
+                    .iter()
+                    .map(|coset| coset.iter().map(element).collect::<Vec<String>>())
+                    .collect::<Vec<Vec<String>>>(),
+                "salts": layer.salts.iter().map(element).collect::<Vec<String>>(),
+                "inclusion_proof": path(&layer.inclusion_proof),
+            })).collect::<Vec<Value>>(),
+            "last_layer_poly": self
+                .last_layer_poly
+                .coefficients()
+                .iter()
+                .map(element)
+                .collect::<Vec<String>>(),
+            "nonce": self.nonce,
+        })
+    }
+}
+
+fn write_multiproof(bytes: &mut Vec<u8>, proof: &MultiProof<[u8; 32]>) {
+    bytes.extend((proof.nodes.len() as u32).to_be_bytes());
+    for node in &proof.nodes {
+        bytes.extend(node);
+    }
+}
+
+fn write_elements<F>(bytes: &mut Vec<u8>, values: &[FieldElement<F>])
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    bytes.extend((values.len() as u32).to_be_bytes());
+    for value in values {
+        bytes.extend(value.to_bytes_be());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ProofDecodeError> {
+        let chunk = self
+            .bytes
+            .get(self.position..self.position + n)
+            .ok_or(ProofDecodeError::UnexpectedEnd)?;
+        self.position += n;
+        Ok(chunk)
+    }
+
+    fn u8(&mut self) -> Result<u8, ProofDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, ProofDecodeError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, ProofDecodeError> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn node(&mut self) -> Result<[u8; 32], ProofDecodeError> {
+        Ok(self.take(32)?.try_into().unwrap())
+    }
+
+    fn field_element<F>(&mut self) -> Result<FieldElement<F>, ProofDecodeError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        FieldElement::from_bytes_be(self.take(element_size::<F>())?)
+            .map_err(|_| ProofDecodeError::InvalidFieldElement)
+    }
+
+    fn multiproof(&mut self) -> Result<MultiProof<[u8; 32]>, ProofDecodeError> {
+        let count = self.u32()?;
+        let nodes = (0..count)
+            .map(|_| self.node())
+            .collect::<Result<Vec<[u8; 32]>, _>>()?;
+        Ok(MultiProof { nodes })
+    }
+
+    fn field_elements<F>(&mut self) -> Result<Vec<FieldElement<F>>, ProofDecodeError>
+    where
+        F: IsField,
+        FieldElement<F>: ByteConversion,
+    {
+        let count = self.u32()?;
+        (0..count).map(|_| self.field_element()).collect()
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt;
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsPrimeField},
+};
+
+use crate::{fri::Coset, merkle::backends::HashFunction};
+
+use super::ProofDecodeError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofOptions {
+    pub blowup_factor: usize,
+    pub number_of_queries: usize,
+    pub fri_folding_factor: usize,
+    pub fri_last_layer_degree_bound: usize,
+    pub coset_offset: u64,
+    pub grinding_bits: usize,
+    pub hash: HashFunction,
+    pub zero_knowledge: bool,
+}
+
+impl Default for ProofOptions {
+    fn default() -> Self {
+        ProofOptions {
+            blowup_factor: 256,
+            number_of_queries: 3,
+            fri_folding_factor: 2,
+            fri_last_layer_degree_bound: 1,
+            coset_offset: 3,
+            grinding_bits: 0,
+            hash: HashFunction::Keccak256,
+            zero_knowledge: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofOptionsError {
+    TraceLengthNotPowerOfTwo(usize),
+    BlowupFactorNotPowerOfTwo(usize),
+    NoQueries,
+    TooManyQueries {
+        queries: usize,
+        domain_size: usize,
+    },
+    UnsupportedFoldingFactor(usize),
+    FoldingFactorExceedsBlowup {
+        folding_factor: usize,
+        blowup_factor: usize,
+    },
+    LastLayerDegreeBoundTooHigh {
+        bound: usize,
+        trace_length: usize,
+    },
+    DomainTooLarge {
+        log_size: u64,
+        two_adicity: u64,
+    },
+    CosetOffsetInDomain(u64),
+    GrindingBitsTooHigh(usize),
+    ZeroKnowledgeBlowupTooSmall(usize),
+    TooManyCompositionParts {
+        parts: usize,
+        blowup_factor: usize,
+    },
+    ZeroKnowledgeTooManyOpenings {
+        openings: usize,
+        trace_length: usize,
+    },
+}
+
+impl fmt::Display for ProofOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofOptionsError::TraceLengthNotPowerOfTwo(n) => {
+                write!(f, "trace length {n} is not a power of two")
+            }
+            ProofOptionsError::BlowupFactorNotPowerOfTwo(b) => {
+                write!(
+                    f,
+                    "blowup factor {b} is not a power of two greater than one"
+                )
+            }
+            ProofOptionsError::NoQueries => write!(f, "at least one query is required"),
+            ProofOptionsError::TooManyQueries {
+                queries,
+                domain_size,
+            } => write!(
+                f,
+                "{queries} queries exceed the lde domain size {domain_size}"
+            ),
+            ProofOptionsError::UnsupportedFoldingFactor(k) => {
+                write!(f, "fri folding factor {k} is not supported, use 2, 4 or 8")
+            }
+            ProofOptionsError::FoldingFactorExceedsBlowup {
+                folding_factor,
+                blowup_factor,
+            } => write!(
+                f,
+                "fri folding factor {folding_factor} exceeds the blowup factor {blowup_factor}"
+            ),
+            ProofOptionsError::LastLayerDegreeBoundTooHigh {
+                bound,
+                trace_length,
+            } => write!(
+                f,
+                "last layer degree bound {bound} must be below the trace length {trace_length}"
+            ),
+            ProofOptionsError::DomainTooLarge {
+                log_size,
+                two_adicity,
+            } => write!(
+                f,
+                "lde domain of size 2^{log_size} exceeds the field two-adicity {two_adicity}"
+            ),
+            ProofOptionsError::CosetOffsetInDomain(offset) => {
+                write!(f, "coset offset {offset} lies in the lde subgroup")
+            }
+            ProofOptionsError::GrindingBitsTooHigh(bits) => {
+                write!(
+                    f,
+                    "{bits} grinding bits exceed the maximum of {MAX_GRINDING_BITS}"
+                )
+            }
+            ProofOptionsError::ZeroKnowledgeBlowupTooSmall(b) => {
+                write!(
+                    f,
+                    "zero knowledge needs a blowup factor of at least 4, got {b}"
+                )
+            }
+            ProofOptionsError::TooManyCompositionParts {
+                parts,
+                blowup_factor,
+            } => write!(
+                f,
+                "the constraints need {parts} composition parts, more than an lde with blowup \
+                 factor {blowup_factor} determines"
+            ),
+            ProofOptionsError::ZeroKnowledgeTooManyOpenings {
+                openings,
+                trace_length,
+            } => write!(
+                f,
+                "zero knowledge allows at most {trace_length} openings per trace column, the \
+                 queries and the out of domain frame open {openings}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProofOptionsError {}
+
+// a nonce search beyond this would outlast any sensible proving time
+pub const MAX_GRINDING_BITS: usize = 32;
+
+impl ProofOptions {
+    pub const ENCODED_LEN: usize = 64;
+
+    pub fn validate<F: IsFFTField>(&self, trace_length: usize) -> Result<(), ProofOptionsError> {
+        if !trace_length.is_power_of_two() {
+            return Err(ProofOptionsError::TraceLengthNotPowerOfTwo(trace_length));
+        }
+        if !self.blowup_factor.is_power_of_two() || self.blowup_factor < 2 {
+            return Err(ProofOptionsError::BlowupFactorNotPowerOfTwo(
+                self.blowup_factor,
+            ));
+        }
+        if self.number_of_queries == 0 {
+            return Err(ProofOptionsError::NoQueries);
+        }
+        if ![2, 4, 8].contains(&self.fri_folding_factor) {
+            return Err(ProofOptionsError::UnsupportedFoldingFactor(
+                self.fri_folding_factor,
+            ));
+        }
+        // the last fri layer is larger than the blowup factor and must hold a whole coset
+        if self.blowup_factor < self.fri_folding_factor {
+            return Err(ProofOptionsError::FoldingFactorExceedsBlowup {
+                folding_factor: self.fri_folding_factor,
+                blowup_factor: self.blowup_factor,
+            });
+        }
+        if self.fri_last_layer_degree_bound >= trace_length {
+            return Err(ProofOptionsError::LastLayerDegreeBoundTooHigh {
+                bound: self.fri_last_layer_degree_bound,
+                trace_length,
+            });
+        }
+        // randomized traces have twice the degree, which a blowup of 2 could not absorb
+        if self.zero_knowledge && self.blowup_factor < 4 {
+            return Err(ProofOptionsError::ZeroKnowledgeBlowupTooSmall(
+                self.blowup_factor,
+            ));
+        }
+        if self.grinding_bits > MAX_GRINDING_BITS {
+            return Err(ProofOptionsError::GrindingBitsTooHigh(self.grinding_bits));
+        }
+
+        let log_size = (trace_length.trailing_zeros() + self.blowup_factor.trailing_zeros()) as u64;
+        if log_size > F::TWO_ADICITY || log_size >= usize::BITS as u64 {
+            return Err(ProofOptionsError::DomainTooLarge {
+                log_size,
+                two_adicity: F::TWO_ADICITY,
+            });
+        }
+        // query sampling draws this many indices, past the domain size they only repeat
+        let domain_size = trace_length * self.blowup_factor;
+        if self.number_of_queries > domain_size {
+            return Err(ProofOptionsError::TooManyQueries {
+                queries: self.number_of_queries,
+                domain_size,
+            });
+        }
+
+        let offset = FieldElement::<F>::from(self.coset_offset);
+        if offset == FieldElement::zero() || offset.pow(domain_size) == FieldElement::one() {
+            return Err(ProofOptionsError::CosetOffsetInDomain(self.coset_offset));
+        }
+
+        Ok(())
+    }
+
+    pub fn lde_domain<F: IsFFTField>(&self, trace_length: usize) -> Coset<F> {
+        let size = trace_length * self.blowup_factor;
+        Coset {
+            generator: F::get_primitive_root_of_unity(size.trailing_zeros() as u64).unwrap(),
+            offset: FieldElement::from(self.coset_offset),
+            size,
+        }
+    }
+
+    // trace polynomials have degree < trace_length, or < 2 trace_length once randomized
+    pub fn trace_degree_bound(&self, trace_length: usize) -> usize {
+        if self.zero_knowledge {
+            2 * trace_length
+        } else {
+            trace_length
+        }
+    }
+
+    // the composition polynomial has degree < parts * trace degree bound, and its lde values
+    // only determine it below the lde size
+    pub fn validate_composition_parts(
+        &self,
+        trace_length: usize,
+        parts: usize,
+    ) -> Result<(), ProofOptionsError> {
+        if parts * self.trace_degree_bound(trace_length) > trace_length * self.blowup_factor {
+            return Err(ProofOptionsError::TooManyCompositionParts {
+                parts,
+                blowup_factor: self.blowup_factor,
+            });
+        }
+        Ok(())
+    }
+
+    // a randomized trace column carries trace_length random coefficients, so at most that many
+    // of its values outside the trace domain look uniform: the out of domain frame and every
+    // query each open `frame_rows` of them
+    pub fn validate_zero_knowledge_openings(
+        &self,
+        trace_length: usize,
+        frame_rows: usize,
+    ) -> Result<(), ProofOptionsError> {
+        let openings = frame_rows.saturating_mul(self.number_of_queries.saturating_add(1));
+        if self.zero_knowledge && openings > trace_length {
+            return Err(ProofOptionsError::ZeroKnowledgeTooManyOpenings {
+                openings,
+                trace_length,
+            });
+        }
+        Ok(())
+    }
+
+    // the composition parts, followed by the masking polynomial in zero knowledge mode
+    pub fn composition_columns(&self, parts: usize) -> usize {
+        parts + self.zero_knowledge as usize
+    }
+
+    // folds needed until a polynomial within the trace degree bound fits the last layer bound,
+    // every fold dividing the degree by the folding factor
+    // at least one layer is committed so the DEEP openings have a merkle root to land on
+    pub fn fri_number_of_folds(&self, trace_length: usize) -> usize {
+        let degree_bound = self.trace_degree_bound(trace_length);
+        let log_arity = self.fri_folding_factor.trailing_zeros() as usize;
+        let mut folds = 1;
+        while (degree_bound >> (folds * log_arity)) > self.fri_last_layer_degree_bound + 1 {
+            folds += 1;
+        }
+        folds
+    }
+
+    // coefficients the last fri layer can have: every fold divides the degree bound by the
+    // folding factor, which can leave it below the requested last layer bound
+    pub fn fri_last_layer_size(&self, trace_length: usize) -> usize {
+        let log_arity = self.fri_folding_factor.trailing_zeros() as usize;
+        let folds = self.fri_number_of_folds(trace_length);
+        (self.trace_degree_bound(trace_length) >> (folds * log_arity)).max(1)
+    }
+
+    // every query brings log2 of the inverse fri rate, halved by the randomized trace degree
+    fn query_security_bits(&self) -> usize {
+        let log_rate = (self.blowup_factor.trailing_zeros() as usize)
+            .saturating_sub(self.zero_knowledge as usize);
+        self.number_of_queries * log_rate
+    }
+
+    // ethSTARK conjecture: every query brings log2(blowup) bits on top of the grinding bits,
+    // capped by the field size
+    pub fn conjectured_security_bits<F: IsPrimeField>(&self) -> usize {
+        (self.query_security_bits() + self.grinding_bits).min(F::field_bit_size())
+    }
+
+    // Johnson bound regime: every query brings half as many bits, and the field has to
+    // absorb the union bound over the lde domain
+    pub fn proven_security_bits<F: IsPrimeField>(&self, trace_length: usize) -> usize {
+        let log_lde_size = (trace_length * self.blowup_factor).trailing_zeros() as usize;
+        (self.query_security_bits() / 2 + self.grinding_bits)
+            .min(F::field_bit_size().saturating_sub(2 * log_lde_size))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [
+            self.blowup_factor as u64,
+            self.number_of_queries as u64,
+            self.fri_folding_factor as u64,
+            self.fri_last_layer_degree_bound as u64,
+            self.coset_offset,
+            self.grinding_bits as u64,
+            self.hash.tag(),
+            self.zero_knowledge as u64,
+        ]
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofDecodeError> {
+        if bytes.len() != Self::ENCODED_LEN {
+            return Err(ProofDecodeError::UnexpectedEnd);
+        }
+        let field = |i: usize| u64::from_be_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap());
+        let hash = HashFunction::from_tag(field(6))
+            .ok_or(ProofDecodeError::UnknownHashFunction(field(6)))?;
+
+        let zero_knowledge = match field(7) {
+            0 => false,
+            1 => true,
+            flag => return Err(ProofDecodeError::InvalidZeroKnowledgeFlag(flag)),
+        };
+
+        Ok(ProofOptions {
+            blowup_factor: field(0) as usize,
+            number_of_queries: field(1) as usize,
+            fri_folding_factor: field(2) as usize,
+            fri_last_layer_degree_bound: field(3) as usize,
+            coset_offset: field(4),
+            grinding_bits: field(5) as usize,
+            hash,
+            zero_knowledge,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+
+    fn options(blowup_factor: usize, number_of_queries: usize) -> ProofOptions {
+        ProofOptions {
+            blowup_factor,
+            number_of_queries,
+            ..ProofOptions::default()
+        }
+    }
+
+    #[test]
+    fn security_bits_follow_queries_and_grinding() {
+        // (options, conjectured, proven) for a trace of 32 rows
+        let cases = [
+            (options(8, 8), 24, 12),
+            (options(256, 3), 24, 12),
+            (
+                ProofOptions {
+                    grinding_bits: 16,
+                    ..options(8, 8)
+                },
+                40,
+                28,
+            ),
+            (
+                ProofOptions {
+                    zero_knowledge: true,
+                    ..options(8, 8)
+                },
+                16,
+                8,
+            ),
+        ];
+
+        for (options, conjectured, proven) in cases {
+            assert_eq!(options.conjectured_security_bits::<F>(), conjectured);
+            assert_eq!(options.proven_security_bits::<F>(32), proven);
+        }
+    }
+
+    #[test]
+    fn security_bits_are_capped_by_the_field() {
+        let options = options(8, 200);
+
+        // 600 conjectured bits, but a 252 bit field
+        assert_eq!(options.conjectured_security_bits::<F>(), 252);
+        // 300 proven bits, but the union bound over 2^8 lde points takes 16 bits of the field
+        assert_eq!(options.proven_security_bits::<F>(32), 236);
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+
+use lambdaworks_crypto::merkle_tree::merkle::MerkleTree;
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsFFTField},
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    constraints::{
+        check::{check_air, check_trace, AirError, ConstraintViolation},
+        composition_degree, composition_parts,
+        evaluator::evaluate_composition_over_lde,
+        Air, Frame,
+    },
+    deep::{
+        append_ood_evaluations, deep_composition_poly, number_of_deep_coefficients, ood_points,
+        sample_ood_point,
+    },
+    fri::commit::commit,
+    merkle::{
+        backends::{
+            Blake2sBackend, CommitmentBackend, HashFunction, KeccakBackend, PoseidonBackend,
+        },
+        MultiProof,
+    },
+    proof::{
+        options::{ProofOptions, ProofOptionsError},
+        QueryOpening, StarkProof,
+    },
+    trace::{compute_lde, TraceTable},
+    transcript::public_transcript,
+    zk::{leaf_salts, random_elements, randomize_trace_poly, salted_leaf},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProveError {
+    InvalidOptions(ProofOptionsError),
+    InvalidAir(AirError),
+    // (rows, columns) the air expects and the trace has
+    TraceShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    ConstraintViolation(ConstraintViolation),
+}
+
+impl fmt::Display for ProveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProveError::InvalidOptions(err) => write!(f, "invalid proof options: {err}"),
+            ProveError::InvalidAir(err) => write!(f, "invalid air: {err}"),
+            ProveError::TraceShapeMismatch { expected, found } => write!(
+                f,
+                "expected a trace of {} rows and {} columns, found {} rows and {} columns",
+                expected.0, expected.1, found.0, found.1
+            ),
+            ProveError::ConstraintViolation(violation) => {
+                write!(f, "trace does not satisfy the air: {violation}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProveError {}
+
+impl From<ProofOptionsError> for ProveError {
+    fn from(err: ProofOptionsError) -> Self {
+        ProveError::InvalidOptions(err)
+    }
+}
+
+impl From<AirError> for ProveError {
+    fn from(err: AirError) -> Self {
+        ProveError::InvalidAir(err)
+    }
+}
+
+impl From<ConstraintViolation> for ProveError {
+    fn from(violation: ConstraintViolation) -> Self {
+        ProveError::ConstraintViolation(violation)
+    }
+}
+
+// in debug builds the trace is checked against the constraints first, so an unsatisfied one
+// is reported with its row instead of surfacing as a rejected proof
+pub fn prove<F, A>(
+    air: &A,
+    trace: &TraceTable<F>,
+    options: &ProofOptions,
+) -> Result<StarkProof<F>, ProveError>
+where
+    F: IsFFTField,
+    A: Air<F> + Sync,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    prove_checked(air, trace, options, cfg!(debug_assertions))
+}
+
+// proves whatever trace it is given, as a dishonest prover would
+#[cfg(test)]
+pub(crate) fn prove_unchecked<F, A>(
+    air: &A,
+    trace: &TraceTable<F>,
+    options: &ProofOptions,
+) -> Result<StarkProof<F>, ProveError>
+where
+    F: IsFFTField,
+    A: Air<F> + Sync,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    prove_checked(air, trace, options, false)
+}
+
+fn prove_checked<F, A>(
+    air: &A,
+    trace: &TraceTable<F>,
+    options: &ProofOptions,
+    check_constraints: bool,
+) -> Result<StarkProof<F>, ProveError>
+where
+    F: IsFFTField,
+    A: Air<F> + Sync,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    options.validate::<F>(air.trace_length())?;
+    check_air(air)?;
+    let parts = composition_parts(air, options.trace_degree_bound(air.trace_length()));
+    options.validate_composition_parts(air.trace_length(), parts)?;
+    options.validate_zero_knowledge_openings(air.trace_length(), air.transition_offsets().len())?;
+    if (trace.n_rows(), trace.n_cols()) != (air.trace_length(), air.trace_width()) {
+        return Err(ProveError::TraceShapeMismatch {
+            expected: (air.trace_length(), air.trace_width()),
+            found: (trace.n_rows(), trace.n_cols()),
+        });
+    }
+    if check_constraints {
+        check_trace(air, trace)?;
+    }
+
+    let proof = match options.hash {
+        HashFunction::Keccak256 => {
+            prove_with::<F, A, KeccakBackend<F>>(air, trace, options, check_constraints)
+        }
+        HashFunction::Blake2s256 => {
+            prove_with::<F, A, Blake2sBackend<F>>(air, trace, options, check_constraints)
+        }
+        HashFunction::Poseidon => {
+            prove_with::<F, A, PoseidonBackend<F>>(air, trace, options, check_constraints)
+        }
+    };
+    Ok(proof)
+}
+
+// H_i gathers the coefficients of H whose degree is i modulo the number of parts
+fn split_composition_poly<F: IsFFTField>(
+    poly: &Polynomial<FieldElement<F>>,
+    parts: usize,
+) -> Vec<Polynomial<FieldElement<F>>> {
+    (0..parts)
+        .map(|i| {
+            let coefficients: Vec<FieldElement<F>> = poly
+                .coefficients()
+                .iter()
+                .skip(i)
+                .step_by(parts)
+                .cloned()
+                .collect();
+            Polynomial::new(&coefficients)
+        })
+        .collect()
+}
+
+fn prove_with<F, A, B>(
+    air: &A,
+    trace: &TraceTable<F>,
+    options: &ProofOptions,
+    check_constraints: bool,
+) -> StarkProof<F>
+where
+    F: IsFFTField,
+    A: Air<F> + Sync,
+    B: CommitmentBackend<F>,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    let trace_degree_bound = options.trace_degree_bound(air.trace_length());
+    let parts = composition_parts(air, trace_degree_bound);
+
+    let lde_domain = options.lde_domain::<F>(air.trace_length());
+    let trace_generator = air.trace_generator();
+    let offsets = air.transition_offsets();
+    let mut transcript = public_transcript(air, options);
+    let mut rng = rand::thread_rng();
+
+    let mut trace_polys = trace.interpolate_columns();
+    if options.zero_knowledge {
+        trace_polys = trace_polys
+            .iter()
+            .map(|poly| randomize_trace_poly(poly, air.trace_length(), &mut rng))
+            .collect();
+    }
+    let trace_lde = compute_lde(&trace_polys, &lde_domain);
+    let trace_lde_rows: Vec<Vec<FieldElement<F>>> = into_iter!(0..lde_domain.size)
+        .map(|i| trace_lde.iter().map(|column| column[i].clone()).collect())
+        .collect();
+    let trace_salts = leaf_salts(options.zero_knowledge, lde_domain.size, &mut rng);
+    let trace_leaves: Vec<Vec<FieldElement<F>>> = iter!(&trace_lde_rows)
+        .enumerate()
+        .map(|(i, row)| salted_leaf(row, trace_salts.get(i)))
+        .collect();
+    let trace_tree = MerkleTree::<B>::build(&trace_leaves);
+    transcript.append_bytes(&trace_tree.root);
+
+    let alphas = transcript.sample_field_elements(air.num_constraints());
+
+    let composition_poly_evals =
+        evaluate_composition_over_lde(air, &trace_lde, &lde_domain, options.blowup_factor, &alphas);
+    let composition_poly =
+        Polynomial::interpolate_offset_fft::<F>(&composition_poly_evals, &lde_domain.offset)
+            .unwrap();
+    // with every constraint satisfied, a higher degree means the air understates its
+    // transition degrees
+    if check_constraints {
+        let expected = composition_degree(air, trace_degree_bound);
+        assert!(
+            composition_poly.degree() <= expected,
+            "composition polynomial has degree {}, above the {expected} expected from the air",
+            composition_poly.degree()
+        );
+    }
+    let mut composition_polys = split_composition_poly(&composition_poly, parts);
+    // a single part is the composition polynomial itself, whose lde is already at hand
+    let mut composition_evals = if parts == 1 {
+        vec![composition_poly_evals]
+    } else {
+        compute_lde(&composition_polys, &lde_domain)
+    };
+    // a random polynomial within the fri degree bound, committed next to the composition
+    // polynomial, whose DEEP quotient blinds every fri layer
+    if options.zero_knowledge {
+        let mask = Polynomial::new(&random_elements::<F, _>(
+            &mut rng,
+            options.trace_degree_bound(air.trace_length()),
+        ));
+        composition_evals.push(compute_lde(std::slice::from_ref(&mask), &lde_domain).remove(0));
+        composition_polys.push(mask);
+    }
+    let composition_salts = leaf_salts(options.zero_knowledge, lde_domain.size, &mut rng);
+    let composition_rows: Vec<Vec<FieldElement<F>>> = into_iter!(0..lde_domain.size)
+        .map(|i| {
+            composition_evals
+                .iter()
+                .map(|column| column[i].clone())
+                .collect()
+        })
+        .collect();
+    let composition_leaves: Vec<Vec<FieldElement<F>>> = iter!(&composition_rows)
+        .enumerate()
+        .map(|(i, row)| salted_leaf(row, composition_salts.get(i)))
+        .collect();
+    let composition_tree = MerkleTree::<B>::build(&composition_leaves);
+    transcript.append_bytes(&composition_tree.root);
+
+    let z = sample_ood_point(&mut transcript, air.trace_length(), parts, &lde_domain);
+    let points = ood_points(&z, &trace_generator, &offsets, parts);
+    let ood_frame = Frame::read_from_polys(&trace_polys, &z, &trace_generator, &offsets);
+    let ood_composition: Vec<FieldElement<F>> = composition_polys
+        .iter()
+        .map(|poly| poly.evaluate(&points.composition))
+        .collect();
+    append_ood_evaluations(&mut transcript, &ood_frame, &ood_composition);
+
+    let gammas = transcript.sample_field_elements(number_of_deep_coefficients(
+        air,
+        options.composition_columns(parts),
+    ));
+    let deep_poly = deep_composition_poly(
+        &trace_polys,
+        &composition_polys,
+        &points,
+        &ood_frame,
+        &ood_composition,
+        &gammas,
+    );
+
+    let fri = commit::<F, B, _>(
+        &deep_poly,
+        &lde_domain,
+        options.fri_number_of_folds(air.trace_length()),
+        options,
+        &mut transcript,
+        &mut rng,
+    );
+    let queries = fri.queries;
+
+    // g^k x sits blowup_factor * k positions after x in the lde domain
+    let trace_positions: Vec<usize> = queries
+        .iter()
+        .flat_map(|q| {
+            offsets
+                .iter()
+                .map(move |offset| (q + offset * options.blowup_factor) % lde_domain.size)
+        })
+        .collect();
+    let openings = iter!(&queries)
+        .enumerate()
+        .map(|(n, q)| {
+            let rows = &trace_positions[n * offsets.len()..(n + 1) * offsets.len()];
+            QueryOpening {
+                trace_frame: rows
+                    .iter()
+                    .map(|row| trace_lde_rows[*row].clone())
+                    .collect(),
+                composition_values: composition_rows[*q].clone(),
+                salts: rows
+                    .iter()
+                    .filter_map(|row| trace_salts.get(*row))
+                    .chain(composition_salts.get(*q))
+                    .cloned()
+                    .collect(),
+            }
+        })
+        .collect();
+
+    StarkProof {
+        options: options.clone(),
+        trace_root: trace_tree.root,
+        composition_root: composition_tree.root,
+        trace_proof: MultiProof::build(&trace_tree, &trace_positions),
+        composition_proof: MultiProof::build(&composition_tree, &queries),
+        ood_trace: ood_frame.rows().to_vec(),
+        ood_composition,
+        openings,
+        layers: fri.layers,
+        last_layer_poly: fri.last_layer_poly,
+        nonce: fri.nonce,
+    }
+}
@@ -0,0 +1,116 @@
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::fri::Coset;
+
+// execution trace stored row-major, `width` cells per row
+#[derive(Clone, Debug)]
+pub struct TraceTable<F: IsField> {
+    data: Vec<FieldElement<F>>,
+    width: usize,
+}
+
+impl<F: IsField> TraceTable<F> {
+    pub fn new(data: Vec<FieldElement<F>>, width: usize) -> Self {
+        assert!(width > 0 && data.len().is_multiple_of(width));
+        Self { data, width }
+    }
+
+    pub fn from_columns(columns: &[Vec<FieldElement<F>>]) -> Self {
+        let n_rows = columns[0].len();
+        assert!(columns.iter().all(|column| column.len() == n_rows));
+
+        let data = (0..n_rows)
+            .flat_map(|row| columns.iter().map(move |column| column[row].clone()))
+            .collect();
+        Self::new(data, columns.len())
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.data.len() / self.width
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> &FieldElement<F> {
+        &self.data[row * self.width + column]
+    }
+
+    pub fn row(&self, row: usize) -> &[FieldElement<F>] {
+        &self.data[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> Vec<&[FieldElement<F>]> {
+        self.data.chunks(self.width).collect()
+    }
+
+    pub fn column(&self, column: usize) -> Vec<FieldElement<F>> {
+        self.data
+            .iter()
+            .skip(column)
+            .step_by(self.width)
+            .cloned()
+            .collect()
+    }
+
+    pub fn columns(&self) -> Vec<Vec<FieldElement<F>>> {
+        (0..self.width).map(|column| self.column(column)).collect()
+    }
+}
+
+impl<F: IsFFTField> TraceTable<F>
+where
+    FieldElement<F>: Send + Sync,
+{
+    // one polynomial per column over the trace domain <g>
+    pub fn interpolate_columns(&self) -> Vec<Polynomial<FieldElement<F>>> {
+        into_iter!(self.columns())
+            .map(|column| Polynomial::interpolate_fft::<F>(&column).unwrap())
+            .collect()
+    }
+}
+
+// per-column evaluations over the lde coset, of column polynomials possibly randomized
+pub fn compute_lde<F>(
+    polys: &[Polynomial<FieldElement<F>>],
+    lde_domain: &Coset<F>,
+) -> Vec<Vec<FieldElement<F>>>
+where
+    F: IsFFTField,
+    FieldElement<F>: Send + Sync,
+{
+    iter!(polys)
+        .map(|poly| {
+            Polynomial::evaluate_offset_fft::<F>(poly, 1, Some(lde_domain.size), &lde_domain.offset)
+                .unwrap()
+        })
+        .collect()
+}
+
+pub fn fibonacci_trace<F: IsField>(length: usize) -> TraceTable<F> {
+    fibonacci_trace_from(FieldElement::one(), FieldElement::one(), length)
+}
+
+pub fn fibonacci_trace_from<F: IsField>(
+    first: FieldElement<F>,
+    second: FieldElement<F>,
+    length: usize,
+) -> TraceTable<F> {
+    let mut column = vec![first, second];
+
+    while column.len() < length {
+        column.push(&column[column.len() - 2] + &column[column.len() - 1]);
+    }
+
+    TraceTable::new(column, 1)
+}
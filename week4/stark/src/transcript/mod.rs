@@ -0,0 +1,113 @@
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    traits::AsBytes,
+};
+use sha3::{Digest, Keccak256};
+
+use crate::{constraints::Air, proof::options::ProofOptions};
+
+pub struct Transcript {
+    hasher: Keccak256,
+}
+
+impl Transcript {
+    pub fn new(domain_separator: &[u8]) -> Self {
+        let mut transcript = Self {
+            hasher: Keccak256::new(),
+        };
+        transcript.append_bytes(domain_separator);
+        transcript
+    }
+
+    pub fn append_bytes(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    pub fn append_field_element<F: IsField>(&mut self, element: &FieldElement<F>)
+    where
+        FieldElement<F>: AsBytes,
+    {
+        self.append_bytes(&element.as_bytes());
+    }
+
+    pub fn state(&self) -> [u8; 32] {
+        self.hasher.clone().finalize().into()
+    }
+
+    // squeezes a digest and absorbs it back so consecutive challenges differ
+    pub fn challenge(&mut self) -> [u8; 32] {
+        let digest: [u8; 32] = self.hasher.finalize_reset().into();
+        self.hasher.update(digest);
+        digest
+    }
+
+    pub fn sample_field_element<F: IsField>(&mut self) -> FieldElement<F> {
+        let base = FieldElement::<F>::from(u64::MAX) + FieldElement::<F>::one();
+        self.challenge()
+            .chunks(8)
+            .fold(FieldElement::zero(), |acc, limb| {
+                acc * &base + FieldElement::from(u64::from_be_bytes(limb.try_into().unwrap()))
+            })
+    }
+
+    pub fn sample_field_elements<F: IsField>(&mut self, n: usize) -> Vec<FieldElement<F>> {
+        (0..n).map(|_| self.sample_field_element()).collect()
+    }
+
+    pub fn sample_index(&mut self, upper_bound: usize) -> usize {
+        let challenge = self.challenge();
+        (u64::from_be_bytes(challenge[..8].try_into().unwrap()) % upper_bound as u64) as usize
+    }
+
+    pub fn sample_indices(&mut self, n: usize, upper_bound: usize) -> Vec<usize> {
+        (0..n).map(|_| self.sample_index(upper_bound)).collect()
+    }
+
+    // a nonce is valid when keccak(state || nonce) starts with `grinding_bits` zero bits
+    pub fn is_valid_nonce(&self, nonce: u64, grinding_bits: usize) -> bool {
+        has_leading_zeros(&self.state(), nonce, grinding_bits)
+    }
+
+    // the smallest valid nonce, also when searching in parallel
+    pub fn grind(&self, grinding_bits: usize) -> u64 {
+        let state = self.state();
+        #[cfg(feature = "parallel")]
+        let nonce = rayon::iter::ParallelIterator::find_first(
+            rayon::iter::IntoParallelIterator::into_par_iter(0..u64::MAX),
+            |nonce| has_leading_zeros(&state, *nonce, grinding_bits),
+        );
+        #[cfg(not(feature = "parallel"))]
+        let nonce = (0..u64::MAX).find(|nonce| has_leading_zeros(&state, *nonce, grinding_bits));
+        nonce.unwrap()
+    }
+}
+
+fn has_leading_zeros(state: &[u8; 32], nonce: u64, bits: usize) -> bool {
+    let digest = Keccak256::new()
+        .chain_update(state)
+        .chain_update(nonce.to_be_bytes())
+        .finalize();
+    u64::from_be_bytes(digest[..8].try_into().unwrap()).leading_zeros() as usize >= bits
+}
+
+// prover and verifier both start from a transcript bound to the public parameters, including
+// the boundary assertions derived from the public inputs
+pub fn public_transcript<F, A>(air: &A, options: &ProofOptions) -> Transcript
+where
+    F: IsFFTField,
+    A: Air<F>,
+    FieldElement<F>: AsBytes,
+{
+    let mut transcript = Transcript::new(b"stark101");
+    transcript.append_bytes(&(air.trace_length() as u64).to_be_bytes());
+    transcript.append_bytes(&options.to_bytes());
+    for assertion in air.boundary_assertions() {
+        transcript.append_bytes(&(assertion.column as u64).to_be_bytes());
+        transcript.append_bytes(&(assertion.row as u64).to_be_bytes());
+        transcript.append_field_element(&assertion.value);
+    }
+    transcript
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsPrimeField},
+    },
+    traits::AsBytes,
+};
+
+use crate::{
+    constraints::{
+        check::{check_air, AirError},
+        composition_parts, eval_composition_polynomial, Air, Frame,
+    },
+    deep::{
+        append_ood_evaluations, eval_deep_composition, number_of_deep_coefficients, ood_points,
+        sample_ood_point,
+    },
+    fri::decommit::{replay_challenges, verify_fri, FriVerifyError},
+    merkle::backends::{
+        Blake2sBackend, CommitmentBackend, HashFunction, KeccakBackend, PoseidonBackend,
+    },
+    proof::{
+        options::{ProofOptions, ProofOptionsError},
+        StarkProof,
+    },
+    transcript::public_transcript,
+    zk::salted_leaf,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum VerifierError {
+    OptionsMismatch,
+    InvalidOptions(ProofOptionsError),
+    InvalidAir(AirError),
+    MalformedOodFrame,
+    OodCompositionMismatch,
+    OpeningCountMismatch { expected: usize, found: usize },
+    MalformedOpening { query: usize },
+    TraceOpeningInvalid,
+    CompositionOpeningInvalid,
+    CompositionMismatch { query: usize },
+    DeepCompositionMismatch { query: usize },
+    Fri(FriVerifyError),
+}
+
+impl fmt::Display for VerifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifierError::OptionsMismatch => {
+                write!(f, "proof options differ from the ones the verifier expects")
+            }
+            VerifierError::InvalidOptions(err) => write!(f, "invalid proof options: {err}"),
+            VerifierError::InvalidAir(err) => write!(f, "invalid air: {err}"),
+            VerifierError::MalformedOodFrame => {
+                write!(f, "out of domain frame does not match the air shape")
+            }
+            VerifierError::OodCompositionMismatch => write!(
+                f,
+                "composition polynomial does not match the constraints at the out of domain point"
+            ),
+            VerifierError::OpeningCountMismatch { expected, found } => {
+                write!(f, "expected {expected} query openings, found {found}")
+            }
+            VerifierError::MalformedOpening { query } => {
+                write!(f, "opening for query {query} does not match the air shape")
+            }
+            VerifierError::TraceOpeningInvalid => write!(f, "invalid trace multiproof"),
+            VerifierError::CompositionOpeningInvalid => {
+                write!(f, "invalid composition multiproof")
+            }
+            VerifierError::CompositionMismatch { query } => write!(
+                f,
+                "opened composition value does not match the constraints for query {query}"
+            ),
+            VerifierError::DeepCompositionMismatch { query } => write!(
+                f,
+                "DEEP composition does not match the first fri layer for query {query}"
+            ),
+            VerifierError::Fri(err) => write!(f, "fri verification failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifierError {}
+
+impl From<ProofOptionsError> for VerifierError {
+    fn from(err: ProofOptionsError) -> Self {
+        VerifierError::InvalidOptions(err)
+    }
+}
+
+impl From<AirError> for VerifierError {
+    fn from(err: AirError) -> Self {
+        VerifierError::InvalidAir(err)
+    }
+}
+
+impl From<FriVerifyError> for VerifierError {
+    fn from(err: FriVerifyError) -> Self {
+        VerifierError::Fri(err)
+    }
+}
+
+// `options` are the ones the verifier requires: the copy carried by the proof only tells
+// which were used, and a forger would pick the weakest
+pub fn verify<F, A>(
+    air: &A,
+    proof: &StarkProof<F>,
+    options: &ProofOptions,
+) -> Result<(), VerifierError>
+where
+    F: IsFFTField + IsPrimeField,
+    A: Air<F>,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    if proof.options != *options {
+        return Err(VerifierError::OptionsMismatch);
+    }
+    match proof.options.hash {
+        HashFunction::Keccak256 => verify_with::<F, A, KeccakBackend<F>>(air, proof),
+        HashFunction::Blake2s256 => verify_with::<F, A, Blake2sBackend<F>>(air, proof),
+        HashFunction::Poseidon => verify_with::<F, A, PoseidonBackend<F>>(air, proof),
+    }
+}
+
+fn verify_with<F, A, B>(air: &A, proof: &StarkProof<F>) -> Result<(), VerifierError>
+where
+    F: IsFFTField + IsPrimeField,
+    A: Air<F>,
+    B: CommitmentBackend<F>,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    let options = &proof.options;
+    options.validate::<F>(air.trace_length())?;
+    check_air(air)?;
+    let parts = composition_parts(air, options.trace_degree_bound(air.trace_length()));
+    options.validate_composition_parts(air.trace_length(), parts)?;
+    options.validate_zero_knowledge_openings(air.trace_length(), air.transition_offsets().len())?;
+    let lde_domain = options.lde_domain::<F>(air.trace_length());
+    let trace_generator = air.trace_generator();
+    let offsets = air.transition_offsets();
+
+    let mut transcript = public_transcript(air, options);
+    transcript.append_bytes(&proof.trace_root);
+    let alphas = transcript.sample_field_elements(air.num_constraints());
+    transcript.append_bytes(&proof.composition_root);
+
+    let composition_columns = options.composition_columns(parts);
+    // one salt per opened trace row and one for the composition leaf
+    let salts_per_opening = if options.zero_knowledge {
+        offsets.len() + 1
+    } else {
+        0
+    };
+
+    let z = sample_ood_point(&mut transcript, air.trace_length(), parts, &lde_domain);
+    if proof.ood_trace.len() != offsets.len()
+        || proof.ood_composition.len() != composition_columns
+        || proof
+            .ood_trace
+            .iter()
+            .any(|row| row.len() != air.trace_width())
+    {
+        return Err(VerifierError::MalformedOodFrame);
+    }
+    let ood_frame = Frame::new(proof.ood_trace.clone());
+    append_ood_evaluations(&mut transcript, &ood_frame, &proof.ood_composition);
+    let gammas =
+        transcript.sample_field_elements(number_of_deep_coefficients(air, composition_columns));
+
+    let number_of_folds = options.fri_number_of_folds(air.trace_length());
+    if proof.layers.len() != number_of_folds {
+        return Err(FriVerifyError::LayerCountMismatch {
+            expected: number_of_folds,
+            found: proof.layers.len(),
+        }
+        .into());
+    }
+
+    let (betas, queries) = replay_challenges(
+        &proof.layers,
+        &proof.last_layer_poly,
+        lde_domain.size,
+        options.number_of_queries,
+        options.grinding_bits,
+        proof.nonce,
+        &mut transcript,
+    )?;
+
+    // the constraints are checked once, at z, against H(z) = sum_i z^i H_i(z^parts)
+    let points = ood_points(&z, &trace_generator, &offsets, parts);
+    let ood_composition = proof.ood_composition[..parts]
+        .iter()
+        .rev()
+        .fold(FieldElement::zero(), |acc, value| acc * &z + value);
+    if eval_composition_polynomial(air, &ood_frame, &z, &alphas) != ood_composition {
+        return Err(VerifierError::OodCompositionMismatch);
+    }
+
+    verify_fri::<F, B>(
+        &proof.layers,
+        &proof.last_layer_poly,
+        &betas,
+        &queries,
+        &lde_domain,
+        options,
+    )?;
+
+    if proof.openings.len() != queries.len() {
+        return Err(VerifierError::OpeningCountMismatch {
+            expected: queries.len(),
+            found: proof.openings.len(),
+        });
+    }
+
+    // the openings tie the first fri layer to the committed trace and composition, and the
+    // trace frame at x, gx, g^2x lets the verifier recompute the composition value itself
+    let mut trace_leaves = vec![];
+    let mut composition_leaves = vec![];
+    for (query, (index, opening)) in queries.iter().zip(&proof.openings).enumerate() {
+        if opening.trace_frame.len() != offsets.len()
+            || opening
+                .trace_frame
+                .iter()
+                .any(|row| row.len() != air.trace_width())
+            || opening.composition_values.len() != composition_columns
+            || opening.salts.len() != salts_per_opening
+        {
+            return Err(VerifierError::MalformedOpening { query });
+        }
+        for (k, (row, offset)) in opening.trace_frame.iter().zip(&offsets).enumerate() {
+            let position = (index + offset * options.blowup_factor) % lde_domain.size;
+            trace_leaves.push((position, salted_leaf(row, opening.salts.get(k))));
+        }
+        composition_leaves.push((
+            *index,
+            salted_leaf(
+                &opening.composition_values,
+                opening.salts.get(offsets.len()),
+            ),
+        ));
+    }
+
+    let depth = lde_domain.size.trailing_zeros() as usize;
+    if !proof
+        .trace_proof
+        .verify::<B>(&proof.trace_root, depth, &trace_leaves)
+    {
+        return Err(VerifierError::TraceOpeningInvalid);
+    }
+    if !proof
+        .composition_proof
+        .verify::<B>(&proof.composition_root, depth, &composition_leaves)
+    {
+        return Err(VerifierError::CompositionOpeningInvalid);
+    }
+
+    for (query, (index, opening)) in queries.iter().zip(&proof.openings).enumerate() {
+        let x = lde_domain.element(*index);
+        let frame = Frame::new(opening.trace_frame.clone());
+        // a single part opens H(x) itself, split parts open H_i(x) and are only tied to the
+        // constraints through the DEEP quotients
+        if parts == 1
+            && eval_composition_polynomial(air, &frame, &x, &alphas)
+                != opening.composition_values[0]
+        {
+            return Err(VerifierError::CompositionMismatch { query });
+        }
+
+        let deep_value = eval_deep_composition(
+            &x,
+            frame.get_row(0),
+            &opening.composition_values,
+            &points,
+            &ood_frame,
+            &proof.ood_composition,
+            &gammas,
+        );
+        if deep_value != *proof.layers[0].query_evaluation(query, *index) {
+            return Err(VerifierError::DeepCompositionMismatch { query });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+};
+use rand::Rng;
+
+// six random limbs folded into the field, so for fields up to 256 bits the bias left by the
+// reduction stays below 2^-128
+pub fn random_element<F: IsField, R: Rng>(rng: &mut R) -> FieldElement<F> {
+    let limb_base = FieldElement::<F>::from(u64::MAX) + FieldElement::<F>::one();
+    (0..6).fold(FieldElement::zero(), |acc, _| {
+        acc * &limb_base + FieldElement::<F>::from(rng.gen::<u64>())
+    })
+}
+
+pub fn random_elements<F: IsField, R: Rng>(rng: &mut R, n: usize) -> Vec<FieldElement<F>> {
+    (0..n).map(|_| random_element(rng)).collect()
+}
+
+// t(x) + (x^n - 1) r(x) with a random r of degree < n: it matches t on the trace domain, so
+// every constraint still holds, while its values anywhere else are those of a trace padded
+// with n random rows
+pub fn randomize_trace_poly<F: IsField, R: Rng>(
+    poly: &Polynomial<FieldElement<F>>,
+    trace_length: usize,
+    rng: &mut R,
+) -> Polynomial<FieldElement<F>> {
+    let r = random_elements::<F, R>(rng, trace_length);
+    let mut coefficients = vec![FieldElement::<F>::zero(); 2 * trace_length];
+    for (i, coefficient) in poly.coefficients().iter().enumerate() {
+        coefficients[i] = coefficient.clone();
+    }
+    for (i, value) in r.iter().enumerate() {
+        coefficients[i] = &coefficients[i] - value;
+        coefficients[trace_length + i] = value.clone();
+    }
+    Polynomial::new(&coefficients)
+}
+
+// one random salt per merkle leaf in zero knowledge mode, none otherwise
+pub fn leaf_salts<F: IsField, R: Rng>(
+    zero_knowledge: bool,
+    leaves: usize,
+    rng: &mut R,
+) -> Vec<FieldElement<F>> {
+    if zero_knowledge {
+        random_elements(rng, leaves)
+    } else {
+        vec![]
+    }
+}
+
+// merkle leaf holding `values` followed by their salt, if any, so that unopened leaves reveal
+// nothing through their hashes
+pub fn salted_leaf<F: IsField>(
+    values: &[FieldElement<F>],
+    salt: Option<&FieldElement<F>>,
+) -> Vec<FieldElement<F>> {
+    values.iter().chain(salt).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        fields::fft_friendly::stark_252_prime_field::Stark252PrimeField, traits::IsFFTField,
+    };
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    #[test]
+    fn randomized_trace_poly_matches_on_the_trace_domain() {
+        let poly = Polynomial::new(&(1..=8).map(FE::from).collect::<Vec<FE>>());
+        let randomized = randomize_trace_poly(&poly, 8, &mut rand::thread_rng());
+        let g = F::get_primitive_root_of_unity(3).unwrap();
+
+        assert_eq!(randomized.degree(), 15);
+        for i in 0..8 {
+            assert_eq!(
+                randomized.evaluate(&g.pow(i as u64)),
+                poly.evaluate(&g.pow(i as u64))
+            );
+        }
+        assert_ne!(
+            randomized.evaluate(&FE::from(3)),
+            poly.evaluate(&FE::from(3))
+        );
+    }
+}
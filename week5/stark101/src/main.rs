@@ -1,96 +1,210 @@
-pub mod constraints;
-pub mod fri;
-pub mod trace;
-
-use constraints::eval_composition_polynomial;
-use fri::{commit::commit, decommit::layers_decommit};
-use lambdaworks_crypto::merkle_tree::backends::types::Keccak256Backend;
-use lambdaworks_math::{
-    field::{
-        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
-        traits::IsFFTField,
-    },
-    polynomial::Polynomial,
+use std::{env, fs, process};
+
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+use serde_json::{json, Value};
+use stark::{
+    constraints::fibonacci::{FibonacciAir, FibonacciPublicInputs},
+    merkle::backends::HashFunction,
+    prove,
+    trace::fibonacci_trace_from,
+    verify, ProofOptions, StarkProof,
 };
-use trace::fibonacci_trace;
 
-const TRACE_LENGTH: usize = 32;
-const DOMAIN_SIZE: usize = 8192;
+type F = Stark252PrimeField;
 
-fn main() {
-    let offset = FieldElement::<Stark252PrimeField>::from(3);
-    let trace_poly_generator = Stark252PrimeField::get_primitive_root_of_unity(5).unwrap();
-    let lde_poly_generator = Stark252PrimeField::get_primitive_root_of_unity(13).unwrap();
-
-    let trace = fibonacci_trace::<Stark252PrimeField>(TRACE_LENGTH);
-
-    let trace_poly = Polynomial::interpolate_fft::<Stark252PrimeField>(&trace).unwrap();
-    println!("trace_poly degree: {}", trace_poly.degree());
-
-    let alphas = vec![FieldElement::from(238), FieldElement::from(912)];
-
-    let (_, composition_poly_evals) = (0..DOMAIN_SIZE).fold(
-        (offset, Vec::<FieldElement<Stark252PrimeField>>::new()),
-        |(eval_point, mut evals), _| {
-            evals.push(eval_composition_polynomial(
-                &trace_poly,
-                &eval_point,
-                &alphas,
-                &trace_poly_generator,
-            ));
-            (eval_point * lde_poly_generator, evals)
-        },
+const USAGE: &str = "usage:
+  stark101 prove <input_file> <proof_file> <public_inputs_file> [--blowup N] [--queries N] [--last-layer-degree N] [--offset N] [--grinding N] [--folding N] [--hash keccak|blake2s|poseidon] [--zk]
+  stark101 verify <proof_file> <public_inputs_file> [same options as prove]
+  stark101 inspect <proof_file> [--json]";
+
+fn parse_usize(value: Option<&String>, name: &str) -> Result<usize, String> {
+    value
+        .ok_or(format!("missing {name}"))?
+        .parse()
+        .map_err(|_| format!("invalid {name}"))
+}
+
+fn parse_options(flags: &[String]) -> Result<ProofOptions, String> {
+    let mut options = ProofOptions::default();
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            "--blowup" => options.blowup_factor = parse_usize(flags.next(), "blowup factor")?,
+            "--queries" => {
+                options.number_of_queries = parse_usize(flags.next(), "number of queries")?
+            }
+            "--last-layer-degree" => {
+                options.fri_last_layer_degree_bound =
+                    parse_usize(flags.next(), "last layer degree")?
+            }
+            "--offset" => options.coset_offset = parse_usize(flags.next(), "coset offset")? as u64,
+            "--grinding" => options.grinding_bits = parse_usize(flags.next(), "grinding bits")?,
+            "--folding" => {
+                options.fri_folding_factor = parse_usize(flags.next(), "folding factor")?
+            }
+            "--hash" => {
+                options.hash = flags
+                    .next()
+                    .and_then(|name| HashFunction::from_name(name))
+                    .ok_or("invalid hash function, use keccak, blake2s or poseidon")?
+            }
+            "--zk" => options.zero_knowledge = true,
+            flag => return Err(format!("unknown flag {flag}")),
+        }
+    }
+    Ok(options)
+}
+
+fn read_proof(path: &str) -> Result<StarkProof<F>, String> {
+    let bytes = fs::read(path).map_err(|err| format!("cannot read {path}: {err}"))?;
+    StarkProof::from_bytes(&bytes).map_err(|err| format!("cannot decode {path}: {err}"))
+}
+
+fn read_json(path: &str) -> Result<Value, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("cannot read {path}: {err}"))?;
+    serde_json::from_str(&text).map_err(|err| format!("cannot parse {path}: {err}"))
+}
+
+fn json_trace_length(value: &Value) -> Result<usize, String> {
+    value["trace_length"]
+        .as_u64()
+        .map(|length| length as usize)
+        .ok_or("missing or invalid trace_length".to_string())
+}
+
+// field elements are hex strings, as printed by `inspect --json`
+fn json_field_element(value: &Value, name: &str) -> Result<FieldElement<F>, String> {
+    value[name]
+        .as_str()
+        .and_then(|hex| FieldElement::<F>::from_hex(hex).ok())
+        .ok_or(format!("missing or invalid {name}"))
+}
+
+fn to_hex(element: &FieldElement<F>) -> String {
+    element.representative().to_string()
+}
+
+fn run_prove(args: &[String]) -> Result<(), String> {
+    let input = read_json(args.first().ok_or("missing input file")?)?;
+    let path = args.get(1).ok_or("missing proof file")?;
+    let public_inputs_path = args.get(2).ok_or("missing public inputs file")?;
+    let options = parse_options(&args[3.min(args.len())..])?;
+
+    let trace_length = json_trace_length(&input)?;
+    options
+        .validate::<F>(trace_length)
+        .map_err(|err| err.to_string())?;
+    if trace_length < 4 {
+        return Err("the trace needs at least 4 rows".to_string());
+    }
+
+    let trace = fibonacci_trace_from(
+        json_field_element(&input, "first")?,
+        json_field_element(&input, "second")?,
+        trace_length,
     );
+    let air = FibonacciAir {
+        trace_length,
+        public_inputs: FibonacciPublicInputs::from_trace(&trace),
+    };
+    let proof_bytes = prove(&air, &trace, &options)
+        .map_err(|err| err.to_string())?
+        .to_bytes();
 
-    let interpolated_cp =
-        Polynomial::interpolate_fft::<Stark252PrimeField>(&composition_poly_evals).unwrap();
+    fs::write(path, &proof_bytes).map_err(|err| format!("cannot write {path}: {err}"))?;
+    let public_inputs = json!({
+        "trace_length": trace_length,
+        "first": to_hex(&air.public_inputs.first),
+        "second": to_hex(&air.public_inputs.second),
+        "result": to_hex(&air.public_inputs.result),
+    });
+    fs::write(public_inputs_path, format!("{public_inputs:#}\n"))
+        .map_err(|err| format!("cannot write {public_inputs_path}: {err}"))?;
+    println!("proof written to {path} ({} bytes)", proof_bytes.len());
+    println!("public inputs written to {public_inputs_path}");
+    println!("result: {}", to_hex(&air.public_inputs.result));
     println!(
-        "composition polynomial degree: {}",
-        interpolated_cp.degree()
+        "security bits: {} conjectured, {} proven",
+        options.conjectured_security_bits::<F>(),
+        options.proven_security_bits::<F>(trace_length)
     );
+    Ok(())
+}
+
+fn run_verify(args: &[String]) -> Result<(), String> {
+    let proof = read_proof(args.first().ok_or("missing proof file")?)?;
+    let public_inputs = read_json(args.get(1).ok_or("missing public inputs file")?)?;
 
-    // randomly selected by verifier
-    let betas: Vec<FieldElement<Stark252PrimeField>> = vec![
-        FieldElement::from(100),
-        FieldElement::from(881),
-        FieldElement::from(331),
-        FieldElement::from(912),
-    ];
+    let trace_length = json_trace_length(&public_inputs)?;
+    let air = FibonacciAir {
+        trace_length,
+        public_inputs: FibonacciPublicInputs {
+            first: json_field_element(&public_inputs, "first")?,
+            second: json_field_element(&public_inputs, "second")?,
+            result: json_field_element(&public_inputs, "result")?,
+        },
+    };
+    // the verifier insists on its own options, whatever the proof file claims
+    let options = parse_options(&args[2.min(args.len())..])?;
+    verify(&air, &proof, &options).map_err(|err| format!("proof rejected: {err}"))?;
+    println!("proof correct");
     println!(
-        "folding betas: {:?}",
-        betas
-            .iter()
-            .map(|b| b.representative().to_hex())
-            .collect::<Vec<String>>()
+        "security bits: {} conjectured, {} proven",
+        options.conjectured_security_bits::<F>(),
+        options.proven_security_bits::<F>(trace_length)
     );
+    Ok(())
+}
 
-    // randomly selected by verifier
-    let queries: Vec<usize> = vec![3892, 1828, 122];
-    println!("fri queries: {:?}", queries);
-
-    let (layers, last_layer_poly) = commit(
-        &betas,
-        &interpolated_cp,
-        &lde_poly_generator,
-        &DOMAIN_SIZE,
-        offset,
-        &queries,
-    );
+fn run_inspect(args: &[String]) -> Result<(), String> {
+    let proof = read_proof(args.first().ok_or("missing proof file")?)?;
 
-    //TODO verifier receives f(x) f(gx) f(g*g*x) calculates cp(x) and check it is present in first layer of FRI
-    let gamma = lde_poly_generator.pow(queries[0]) * offset;
-    let cp_gamma = eval_composition_polynomial(&trace_poly, &gamma, &alphas, &trace_poly_generator);
-    assert!(
-        layers[0].x_inclusion_proof[0].verify::<Keccak256Backend<Stark252PrimeField>>(
-            &layers[0].merkle_root,
-            queries[0],
-            &cp_gamma
-        )
+    if args.get(1).is_some_and(|flag| flag == "--json") {
+        println!("{:#}", proof.to_json());
+        return Ok(());
+    }
+
+    println!("options: {:?}", proof.options);
+    println!("trace root: {}", hex::encode(proof.trace_root));
+    println!("composition root: {}", hex::encode(proof.composition_root));
+    println!(
+        "out of domain frame: {} rows, {} composition columns, {} queries opened",
+        proof.ood_trace.len(),
+        proof.ood_composition.len(),
+        proof.openings.len()
+    );
+    for (i, layer) in proof.layers.iter().enumerate() {
+        println!(
+            "layer {i}: domain size {}, root {}",
+            layer.domain_size,
+            hex::encode(layer.merkle_root)
+        );
+    }
+    println!(
+        "last layer polynomial: {} coefficients",
+        proof.last_layer_poly.coefficients().len()
     );
+    println!("proof of work nonce: {}", proof.nonce);
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    // Verify FRI
-    layers_decommit(&layers, &betas, &queries, lde_poly_generator);
-    assert!(last_layer_poly.degree() <= trace_poly.degree() / 2_usize.pow(betas.len() as u32));
+    let result = match args.first().map(String::as_str) {
+        Some("prove") => run_prove(&args[1..]),
+        Some("verify") => run_verify(&args[1..]),
+        Some("inspect") => run_inspect(&args[1..]),
+        _ => {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        }
+    };
 
-    println!("proof correct");
+    if let Err(err) = result {
+        eprintln!("{err}");
+        process::exit(1);
+    }
 }